@@ -0,0 +1,89 @@
+//! Canonicalizing MAP builder — turns unsorted, possibly duplicate-keyed
+//! entries into a canonical `MapValue::Map` the encoder will accept.
+//!
+//! `MapValue::Map` requires pre-sorted, duplicate-free entries, and
+//! `mcf_encode_value` rejects anything else with `ERR_DUP_KEY`/
+//! `ERR_KEY_ORDER` (§3.5, §3.6). Real producers often hand us unsorted
+//! input with accidental repeats; `canonicalize_map` sorts and resolves
+//! duplicates instead of always failing, recursing into nested maps so
+//! the whole tree comes out canonical and passes `mcf_encode_value`
+//! unchanged.
+
+use crate::errors::*;
+use crate::value::MapValue;
+
+/// How to resolve duplicate keys when canonicalizing unsorted input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DupPolicy {
+    /// Current `mcf_encode_value` behavior: any duplicate is `ERR_DUP_KEY`.
+    Reject,
+    /// Later entries overwrite earlier ones.
+    LastWins,
+    /// Earlier entries win; later duplicates for the same key are discarded.
+    FirstWins,
+}
+
+/// Sort `entries` into canonical key order (raw UTF-8 byte order, matching
+/// `key_cmp`), resolving duplicates per `policy`, and recurse into nested
+/// `MapValue::Map`/`MapValue::List` children so the whole tree is
+/// canonical.
+///
+/// Collisions are resolved by a left-to-right scan that folds each entry
+/// into an in-progress index, in insertion order — NOT by sorting first
+/// and inspecting neighbors. `LastWins`/`FirstWins` are defined in terms
+/// of *insertion order*, exactly like `HashMap::from([...])`'s left-fold
+/// semantics; sorting first and then picking a neighbor would make the
+/// winner depend on however the sort happens to order equal keys, which
+/// silently flips the intended winner. That divergence between
+/// implementations is exactly the kind of thing that has enabled
+/// parser-divergence exploits elsewhere, so the fold must run before any
+/// sort.
+pub fn canonicalize_map(
+    entries: Vec<(String, MapValue)>,
+    policy: DupPolicy,
+) -> Result<MapValue, MapError> {
+    let mut folded: Vec<(String, MapValue)> = Vec::with_capacity(entries.len());
+
+    for (key, value) in entries {
+        let value = canonicalize_value(value, policy)?;
+        match folded.iter().position(|(k, _)| *k == key) {
+            Some(pos) => match policy {
+                DupPolicy::Reject => {
+                    return Err(MapError::new(
+                        ERR_DUP_KEY,
+                        format!("duplicate key: {}", key),
+                    ));
+                }
+                DupPolicy::LastWins => folded[pos].1 = value,
+                DupPolicy::FirstWins => {}
+            },
+            None => folded.push((key, value)),
+        }
+    }
+
+    folded.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+    Ok(MapValue::Map(folded))
+}
+
+/// Recurse canonicalization into nested containers; scalars pass through.
+///
+/// `pub(crate)` so ingestion paths that don't already have a root MAP's
+/// entries in hand (e.g. the JSON normalization adapter) can canonicalize
+/// an arbitrary `MapValue` tree without unwrapping it themselves.
+pub(crate) fn canonicalize_value(val: MapValue, policy: DupPolicy) -> Result<MapValue, MapError> {
+    match val {
+        MapValue::Map(entries) => canonicalize_map(entries, policy),
+        MapValue::List(items) => {
+            let mut out = Vec::with_capacity(items.len());
+            for item in items {
+                out.push(canonicalize_value(item, policy)?);
+            }
+            Ok(MapValue::List(out))
+        }
+        other => Ok(other),
+    }
+}
+
+// TODO: the duplicate scan is O(n) per entry (O(n^2) per map level); fine
+// for typical descriptor sizes (10-50 keys) but worth revisiting with an
+// index map if this ever sees maps with many thousands of entries.
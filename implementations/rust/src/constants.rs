@@ -23,6 +23,11 @@ pub const TAG_MAP: u8 = 0x04;
 pub const TAG_BOOLEAN: u8 = 0x05;
 /// v1.1: payload is int64 big-endian, always 8 bytes
 pub const TAG_INTEGER: u8 = 0x06;
+/// v1.1 extension: arbitrary-precision integer. Payload is a sign byte
+/// (0x00 non-negative, 0x01 negative), a LEB128 varint magnitude length,
+/// then that many big-endian magnitude bytes with no leading zero byte
+/// (zero is length 0, sign 0x00).
+pub const TAG_BIGINT: u8 = 0x07;
 
 // ── Normative safety limits (§4) ─────────────────────────────
 // These exist to prevent DoS via deeply nested or oversized inputs.
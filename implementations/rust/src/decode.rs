@@ -16,12 +16,10 @@
 use crate::constants::*;
 use crate::encode::validate_utf8_scalar_bytes;
 use crate::errors::*;
-
-// TODO: zero-copy canon_bytes validation — currently we allocate strings
-// for key comparison; could instead compare raw byte slices in-place.
+use crate::value::MapValue;
 
 /// Read an unsigned 32-bit big-endian integer from `buf` at `off`.
-fn read_u32be(buf: &[u8], off: usize) -> Result<(u32, usize), MapError> {
+pub(crate) fn read_u32be(buf: &[u8], off: usize) -> Result<(u32, usize), MapError> {
     if off + 4 > buf.len() {
         return Err(MapError::new(ERR_CANON_MCF, "truncated u32"));
     }
@@ -29,6 +27,34 @@ fn read_u32be(buf: &[u8], off: usize) -> Result<(u32, usize), MapError> {
     Ok((val, off + 4))
 }
 
+/// Read an unsigned LEB128 varint from `buf` at `off` (BIGINT's length
+/// prefix; every other MCF length/count field is a fixed uint32be).
+/// Returns the value and the offset just past it. A non-minimal encoding
+/// (a final 0x00 byte after a continuation byte) is `ERR_CANON_MCF`.
+fn read_varint(buf: &[u8], off: usize) -> Result<(u64, usize), MapError> {
+    let mut value: u64 = 0;
+    let mut shift = 0u32;
+    let mut i = off;
+    loop {
+        if i >= buf.len() {
+            return Err(MapError::new(ERR_CANON_MCF, "truncated varint"));
+        }
+        let byte = buf[i];
+        value |= ((byte & 0x7f) as u64) << shift;
+        i += 1;
+        if byte & 0x80 == 0 {
+            if byte == 0 && i - off > 1 {
+                return Err(MapError::new(ERR_CANON_MCF, "non-minimal varint"));
+            }
+            return Ok((value, i));
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(MapError::new(ERR_CANON_MCF, "varint too long"));
+        }
+    }
+}
+
 /// Decode one MCF value from `buf` at `off`.  Returns the new offset.
 ///
 /// Depth semantics mirror the encoder: root starts at 0, containers
@@ -94,7 +120,10 @@ pub fn mcf_decode_validate(buf: &[u8], off: usize, depth: u32) -> Result<usize,
                 ));
             }
 
-            let mut prev_key: Option<Vec<u8>> = None;
+            // Offsets of the previous key within `buf`, not its bytes —
+            // the current key is already a slice of `buf`, so comparing
+            // `&buf[prev_start..prev_end]` against it needs no allocation.
+            let mut prev_key_range: Option<(usize, usize)> = None;
             for _ in 0..count {
                 // Keys must be STRING-tagged per §3.2
                 if off >= buf.len() {
@@ -110,13 +139,14 @@ pub fn mcf_decode_validate(buf: &[u8], off: usize, depth: u32) -> Result<usize,
                 if key_off + key_len > buf.len() {
                     return Err(MapError::new(ERR_CANON_MCF, "truncated string payload"));
                 }
-                let key_bytes = &buf[key_off..key_off + key_len];
-                validate_utf8_scalar_bytes(key_bytes)?;
-                off = key_off + key_len;
+                let key_start = key_off;
+                let key_end = key_off + key_len;
+                validate_utf8_scalar_bytes(&buf[key_start..key_end])?;
+                off = key_end;
 
                 // Enforce ordering and uniqueness on the wire (§3.5, §3.6)
-                if let Some(ref prev) = prev_key {
-                    match prev.as_slice().cmp(key_bytes) {
+                if let Some((prev_start, prev_end)) = prev_key_range {
+                    match buf[prev_start..prev_end].cmp(&buf[key_start..key_end]) {
                         std::cmp::Ordering::Equal => {
                             return Err(MapError::new(ERR_DUP_KEY, "duplicate key in MCF"));
                         }
@@ -129,7 +159,7 @@ pub fn mcf_decode_validate(buf: &[u8], off: usize, depth: u32) -> Result<usize,
                         std::cmp::Ordering::Less => {}
                     }
                 }
-                prev_key = Some(key_bytes.to_vec());
+                prev_key_range = Some((key_start, key_end));
 
                 // Decode the value
                 off = mcf_decode_validate(buf, off, depth + 1)?;
@@ -163,9 +193,241 @@ pub fn mcf_decode_validate(buf: &[u8], off: usize, depth: u32) -> Result<usize,
             Ok(off + 8)
         }
 
+        TAG_BIGINT => {
+            // Sign byte, LEB128 length, minimal big-endian magnitude.
+            // Non-minimal encodings are malformed, not merely a different
+            // value, so they're ERR_CANON_MCF — the same way an invalid
+            // BOOLEAN payload byte is above, not some other error.
+            if off >= buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated bigint sign byte"));
+            }
+            let sign = buf[off];
+            if sign != 0x00 && sign != 0x01 {
+                return Err(MapError::new(
+                    ERR_CANON_MCF,
+                    format!("invalid bigint sign byte 0x{:02x}", sign),
+                ));
+            }
+            let (len, new_off) = read_varint(buf, off + 1)?;
+            let len = len as usize;
+            if new_off + len > buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated bigint magnitude"));
+            }
+            if len == 0 && sign == 0x01 {
+                return Err(MapError::new(ERR_CANON_MCF, "negative-zero BigInt"));
+            }
+            if len > 0 && buf[new_off] == 0 {
+                return Err(MapError::new(ERR_CANON_MCF, "non-minimal BigInt magnitude"));
+            }
+            Ok(new_off + len)
+        }
+
         _ => Err(MapError::new(
             ERR_CANON_MCF,
             format!("unknown MCF tag 0x{:02x}", tag),
         )),
     }
 }
+
+/// Decode one MCF value from `buf` at `off`, materializing it into a
+/// `MapValue`. Returns the value and the new offset.
+///
+/// Mirrors `mcf_decode_validate`'s cursor and limit checks exactly (same
+/// depth/size/ordering/uniqueness enforcement) — this just also builds the
+/// node instead of discarding it. The two walks are separate copies of the
+/// same checks; only their shared helpers (`read_u32be`, `read_varint`,
+/// the UTF-8 check) are common code.
+pub fn mcf_decode_value(buf: &[u8], off: usize, depth: u32) -> Result<(MapValue, usize), MapError> {
+    if off >= buf.len() {
+        return Err(MapError::new(ERR_CANON_MCF, "truncated tag"));
+    }
+    let tag = buf[off];
+    let mut off = off + 1;
+
+    match tag {
+        TAG_STRING => {
+            let (n, new_off) = read_u32be(buf, off)?;
+            off = new_off;
+            let n = n as usize;
+            if off + n > buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated string payload"));
+            }
+            validate_utf8_scalar_bytes(&buf[off..off + n])?;
+            let s = String::from_utf8(buf[off..off + n].to_vec()).expect("validated above");
+            Ok((MapValue::String(s), off + n))
+        }
+
+        TAG_BYTES => {
+            let (n, new_off) = read_u32be(buf, off)?;
+            off = new_off;
+            let n = n as usize;
+            if off + n > buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated bytes payload"));
+            }
+            Ok((MapValue::Bytes(buf[off..off + n].to_vec()), off + n))
+        }
+
+        TAG_LIST => {
+            if depth + 1 > MAX_DEPTH {
+                return Err(MapError::new(ERR_LIMIT_DEPTH, "depth exceeds MAX_DEPTH"));
+            }
+            let (count, new_off) = read_u32be(buf, off)?;
+            off = new_off;
+            if count > MAX_LIST_ENTRIES {
+                return Err(MapError::new(
+                    ERR_LIMIT_SIZE,
+                    "list entry count exceeds limit",
+                ));
+            }
+            let mut items = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let (item, new_off) = mcf_decode_value(buf, off, depth + 1)?;
+                items.push(item);
+                off = new_off;
+            }
+            Ok((MapValue::List(items), off))
+        }
+
+        TAG_MAP => {
+            if depth + 1 > MAX_DEPTH {
+                return Err(MapError::new(ERR_LIMIT_DEPTH, "depth exceeds MAX_DEPTH"));
+            }
+            let (count, new_off) = read_u32be(buf, off)?;
+            off = new_off;
+            if count > MAX_MAP_ENTRIES {
+                return Err(MapError::new(
+                    ERR_LIMIT_SIZE,
+                    "map entry count exceeds limit",
+                ));
+            }
+
+            let mut entries: Vec<(String, MapValue)> = Vec::with_capacity(count as usize);
+            let mut prev_key_range: Option<(usize, usize)> = None;
+            for _ in 0..count {
+                if off >= buf.len() {
+                    return Err(MapError::new(ERR_CANON_MCF, "truncated map key tag"));
+                }
+                if buf[off] != TAG_STRING {
+                    return Err(MapError::new(ERR_SCHEMA, "map key must be STRING"));
+                }
+
+                let (key_len, key_off) = read_u32be(buf, off + 1)?;
+                let key_len = key_len as usize;
+                if key_off + key_len > buf.len() {
+                    return Err(MapError::new(ERR_CANON_MCF, "truncated string payload"));
+                }
+                let key_start = key_off;
+                let key_end = key_off + key_len;
+                validate_utf8_scalar_bytes(&buf[key_start..key_end])?;
+                off = key_end;
+
+                if let Some((prev_start, prev_end)) = prev_key_range {
+                    match buf[prev_start..prev_end].cmp(&buf[key_start..key_end]) {
+                        std::cmp::Ordering::Equal => {
+                            return Err(MapError::new(ERR_DUP_KEY, "duplicate key in MCF"));
+                        }
+                        std::cmp::Ordering::Greater => {
+                            return Err(MapError::new(
+                                ERR_KEY_ORDER,
+                                "key order violation in MCF",
+                            ));
+                        }
+                        std::cmp::Ordering::Less => {}
+                    }
+                }
+                prev_key_range = Some((key_start, key_end));
+
+                let key = String::from_utf8(buf[key_start..key_end].to_vec()).expect("validated above");
+                let (value, new_off) = mcf_decode_value(buf, off, depth + 1)?;
+                entries.push((key, value));
+                off = new_off;
+            }
+            Ok((MapValue::Map(entries), off))
+        }
+
+        TAG_BOOLEAN => {
+            if off >= buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated boolean payload"));
+            }
+            let payload = buf[off];
+            if payload != 0x00 && payload != 0x01 {
+                return Err(MapError::new(
+                    ERR_CANON_MCF,
+                    format!("invalid boolean payload 0x{:02x}", payload),
+                ));
+            }
+            Ok((MapValue::Boolean(payload == 0x01), off + 1))
+        }
+
+        TAG_INTEGER => {
+            if off + 8 > buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated integer payload"));
+            }
+            let mut b = [0u8; 8];
+            b.copy_from_slice(&buf[off..off + 8]);
+            Ok((MapValue::Integer(i64::from_be_bytes(b)), off + 8))
+        }
+
+        TAG_BIGINT => {
+            if off >= buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated bigint sign byte"));
+            }
+            let sign = buf[off];
+            if sign != 0x00 && sign != 0x01 {
+                return Err(MapError::new(
+                    ERR_CANON_MCF,
+                    format!("invalid bigint sign byte 0x{:02x}", sign),
+                ));
+            }
+            let (len, new_off) = read_varint(buf, off + 1)?;
+            let len = len as usize;
+            if new_off + len > buf.len() {
+                return Err(MapError::new(ERR_CANON_MCF, "truncated bigint magnitude"));
+            }
+            if len == 0 && sign == 0x01 {
+                return Err(MapError::new(ERR_CANON_MCF, "negative-zero BigInt"));
+            }
+            if len > 0 && buf[new_off] == 0 {
+                return Err(MapError::new(ERR_CANON_MCF, "non-minimal BigInt magnitude"));
+            }
+            let magnitude = buf[new_off..new_off + len].to_vec();
+            Ok((MapValue::BigInt(sign == 0x01, magnitude), new_off + len))
+        }
+
+        _ => Err(MapError::new(
+            ERR_CANON_MCF,
+            format!("unknown MCF tag 0x{:02x}", tag),
+        )),
+    }
+}
+
+/// Validate `canon` as CANON_HDR + MCF and reconstruct the root `MapValue`
+/// (the inverse of `canon_bytes_from_value`).
+///
+/// Enforces the same limits `mcf_decode_validate`/`mid_from_canon_bytes`
+/// do (depth, size, key ordering/uniqueness) and rejects trailing bytes
+/// after the root value. Re-encoding the result via `canon_bytes_from_value`
+/// reproduces `canon` byte-for-byte, since both sides agree on the same
+/// minimal/canonical wire form.
+pub fn value_from_canon_bytes(canon: &[u8]) -> Result<MapValue, MapError> {
+    if canon.len() > MAX_CANON_BYTES {
+        return Err(MapError::new(
+            ERR_LIMIT_SIZE,
+            "canon bytes exceed MAX_CANON_BYTES",
+        ));
+    }
+    if !canon.starts_with(CANON_HDR) {
+        return Err(MapError::new(ERR_CANON_HDR, "bad CANON_HDR"));
+    }
+
+    let off = CANON_HDR.len();
+    let (value, end) = mcf_decode_value(canon, off, 0)?;
+    if end != canon.len() {
+        return Err(MapError::new(
+            ERR_CANON_MCF,
+            "trailing bytes after MCF root",
+        ));
+    }
+
+    Ok(value)
+}
@@ -11,15 +11,15 @@
 //!   MAP     : 0x04 || uint32be(count)    || (key_1 || val_1) || ... || (key_n || val_n)
 //!   BOOLEAN : 0x05 || payload_byte (0x01 for true, 0x00 for false)
 //!   INTEGER : 0x06 || int64be(value)
+//!   BIGINT  : 0x07 || sign_byte (0x00/0x01) || leb128(magnitude_len) || magnitude
+
+use std::io::Write;
 
 use crate::constants::*;
 use crate::errors::*;
+use crate::string_profile::{validate_value_profile, StringProfile};
 use crate::value::MapValue;
 
-// TODO: consider implementing the Write trait for streaming encode,
-// which would allow writing directly to a sha2 hasher and cutting
-// peak memory usage roughly in half for large descriptors.
-
 /// Validate that a string contains only valid UTF-8 scalar values (§3.4).
 ///
 /// Rust strings are always valid UTF-8, but the spec also requires
@@ -60,7 +60,7 @@ fn key_cmp(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
 }
 
 /// Assert that keys are strictly ascending by memcmp (no duplicates).
-fn ensure_sorted_unique(keys: &[&[u8]]) -> Result<(), MapError> {
+pub(crate) fn ensure_sorted_unique(keys: &[&[u8]]) -> Result<(), MapError> {
     for i in 1..keys.len() {
         match key_cmp(keys[i - 1], keys[i]) {
             std::cmp::Ordering::Equal => {
@@ -75,19 +75,46 @@ fn ensure_sorted_unique(keys: &[&[u8]]) -> Result<(), MapError> {
     Ok(())
 }
 
-/// Encode a canonical-model value into MCF bytes.
+/// Write `buf` to `out`, mapping any I/O failure to `ERR_IO` rather than
+/// swallowing it.
+fn write_io<W: Write>(out: &mut W, buf: &[u8]) -> Result<(), MapError> {
+    out.write_all(buf)
+        .map_err(|e| MapError::new(ERR_IO, format!("write to sink failed: {}", e)))
+}
+
+/// Write `n` to `out` as an unsigned LEB128 varint (used by BIGINT's
+/// length prefix; every other container/length field in MCF is a fixed
+/// uint32be).
+pub(crate) fn write_varint<W: Write>(out: &mut W, mut n: u64) -> Result<(), MapError> {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            return write_io(out, &[byte]);
+        }
+        write_io(out, &[byte | 0x80])?;
+    }
+}
+
+/// Encode a canonical-model value directly into a `Write` sink, with no
+/// intermediate buffer.
+///
+/// This is the primitive `mcf_encode_value` is built on: callers who only
+/// need a digest (e.g. signing or content-addressed storage) can pass a
+/// `sha2::Sha256` (or any other `Write` adapter) and never materialize the
+/// full MCF byte string. Validation — UTF-8/surrogate checks, sorted-unique
+/// keys, depth and entry-count limits — runs in the exact same order as
+/// `mcf_encode_value` so error precedence is unchanged.
 ///
 /// The `depth` parameter tracks container nesting:
 ///   - Root call starts at depth 0.
 ///   - Entering a MAP or LIST checks depth + 1 against MAX_DEPTH.
 ///   - Scalars (STRING, BYTES, BOOLEAN, INTEGER) don't increment depth.
-// TODO: benchmark Vec::with_capacity pre-sizing for typical descriptor
-// shapes (10-50 keys, 2-3 nesting levels) to reduce reallocation.
-pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError> {
+pub fn mcf_encode_to<W: Write>(val: &MapValue, depth: u32, out: &mut W) -> Result<(), MapError> {
     match val {
         MapValue::Boolean(b) => {
             // §3.2: BOOLEAN is 0x05 followed by 0x01 (true) or 0x00 (false).
-            Ok(vec![TAG_BOOLEAN, if *b { 0x01 } else { 0x00 }])
+            write_io(out, &[TAG_BOOLEAN, if *b { 0x01 } else { 0x00 }])
         }
 
         MapValue::Integer(i) => {
@@ -95,10 +122,8 @@ pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError>
             // i64::to_be_bytes() gives two's complement big-endian, which is
             // exactly what the spec requires.  No sign-to-unsigned conversion
             // needed — Rust guarantees two's complement for integer types.
-            let mut buf = Vec::with_capacity(9);
-            buf.push(TAG_INTEGER);
-            buf.extend_from_slice(&i.to_be_bytes());
-            Ok(buf)
+            write_io(out, &[TAG_INTEGER])?;
+            write_io(out, &i.to_be_bytes())
         }
 
         MapValue::String(s) => {
@@ -108,11 +133,9 @@ pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError>
             if len > u32::MAX as usize {
                 return Err(MapError::new(ERR_CANON_MCF, "string length exceeds u32"));
             }
-            let mut buf = Vec::with_capacity(1 + 4 + len);
-            buf.push(TAG_STRING);
-            buf.extend_from_slice(&(len as u32).to_be_bytes());
-            buf.extend_from_slice(raw);
-            Ok(buf)
+            write_io(out, &[TAG_STRING])?;
+            write_io(out, &(len as u32).to_be_bytes())?;
+            write_io(out, raw)
         }
 
         MapValue::Bytes(b) => {
@@ -120,11 +143,29 @@ pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError>
             if len > u32::MAX as usize {
                 return Err(MapError::new(ERR_CANON_MCF, "bytes length exceeds u32"));
             }
-            let mut buf = Vec::with_capacity(1 + 4 + len);
-            buf.push(TAG_BYTES);
-            buf.extend_from_slice(&(len as u32).to_be_bytes());
-            buf.extend_from_slice(b);
-            Ok(buf)
+            write_io(out, &[TAG_BYTES])?;
+            write_io(out, &(len as u32).to_be_bytes())?;
+            write_io(out, b)
+        }
+
+        MapValue::BigInt(negative, magnitude) => {
+            // §3.2 extension: sign byte, LEB128 length, minimal magnitude.
+            // Non-minimal encodings (a leading zero magnitude byte, or a
+            // negative zero) are rejected here so the wire form stays
+            // bijective — the same reason `ensure_sorted_unique` rejects
+            // unsorted MAP keys rather than silently re-sorting them.
+            if *negative && magnitude.is_empty() {
+                return Err(MapError::new(ERR_CANON_MCF, "negative-zero BigInt"));
+            }
+            if magnitude.first() == Some(&0) {
+                return Err(MapError::new(
+                    ERR_CANON_MCF,
+                    "BigInt magnitude has a leading zero byte",
+                ));
+            }
+            write_io(out, &[TAG_BIGINT, if *negative { 0x01 } else { 0x00 }])?;
+            write_varint(out, magnitude.len() as u64)?;
+            write_io(out, magnitude)
         }
 
         MapValue::List(items) => {
@@ -137,13 +178,12 @@ pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError>
                     "list entry count exceeds limit",
                 ));
             }
-            let mut buf = Vec::new();
-            buf.push(TAG_LIST);
-            buf.extend_from_slice(&(items.len() as u32).to_be_bytes());
+            write_io(out, &[TAG_LIST])?;
+            write_io(out, &(items.len() as u32).to_be_bytes())?;
             for item in items {
-                buf.extend(mcf_encode_value(item, depth + 1)?);
+                mcf_encode_to(item, depth + 1, out)?;
             }
-            Ok(buf)
+            Ok(())
         }
 
         MapValue::Map(entries) => {
@@ -167,21 +207,114 @@ pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError>
             // Verify keys are sorted and unique (§3.5, §3.6)
             ensure_sorted_unique(&key_bytes)?;
 
-            let mut buf = Vec::new();
-            buf.push(TAG_MAP);
-            buf.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+            write_io(out, &[TAG_MAP])?;
+            write_io(out, &(entries.len() as u32).to_be_bytes())?;
             for (k, v) in entries {
                 // Keys are always STRING-tagged (§3.2)
                 let raw = k.as_bytes();
-                buf.push(TAG_STRING);
-                buf.extend_from_slice(&(raw.len() as u32).to_be_bytes());
-                buf.extend_from_slice(raw);
-                buf.extend(mcf_encode_value(v, depth + 1)?);
+                write_io(out, &[TAG_STRING])?;
+                write_io(out, &(raw.len() as u32).to_be_bytes())?;
+                write_io(out, raw)?;
+                mcf_encode_to(v, depth + 1, out)?;
             }
-            Ok(buf)
+            Ok(())
         }
     }
 }
 
+/// Encode a canonical-model value into MCF bytes.
+///
+/// Thin wrapper over [`mcf_encode_to`] that writes into a `Vec<u8>`, for
+/// callers that want the byte string itself rather than a streamed digest.
+pub fn mcf_encode_value(val: &MapValue, depth: u32) -> Result<Vec<u8>, MapError> {
+    let mut buf = Vec::new();
+    mcf_encode_to(val, depth, &mut buf)?;
+    Ok(buf)
+}
+
+/// `Write` wrapper that counts bytes passed through it and, the instant the
+/// running total would cross `limit`, stops forwarding to `inner` and
+/// records that the limit — not the sink — is why the write failed.
+///
+/// `mcf_encode_to` routes every write through [`write_io`], which maps any
+/// `io::Error` to `ERR_IO`; that would swallow a deliberate over-limit
+/// signal as an ordinary sink failure, so `mcf_encode_to_writer` checks
+/// `exceeded` after the call and remaps the error to `ERR_LIMIT_SIZE`
+/// itself rather than trying to smuggle the real code through an
+/// `io::Error`.
+struct LimitedWriter<'w, W: Write> {
+    inner: &'w mut W,
+    written: usize,
+    limit: usize,
+    exceeded: bool,
+}
+
+impl<'w, W: Write> Write for LimitedWriter<'w, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written + buf.len() > self.limit {
+            self.exceeded = true;
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "MAX_CANON_BYTES exceeded",
+            ));
+        }
+        self.inner.write_all(buf)?;
+        self.written += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Encode a canonical-model value straight into `w`, enforcing
+/// `MAX_CANON_BYTES` against the running MCF body length as bytes are
+/// produced, and returning the total body length written.
+///
+/// Checking the length only after the whole body is already encoded (as
+/// `mcf_encode_value` + a length check would) still lets a malicious
+/// oversized tree force a multi-gigabyte allocation, or hash, before the
+/// limit is ever noticed. This fails fast with `ERR_LIMIT_SIZE` the
+/// instant the threshold is crossed instead. The limit is checked against
+/// the body alone, with `CANON_HDR`'s fixed length already subtracted, so
+/// the caller doesn't need to pass it in.
+pub fn mcf_encode_to_writer<W: Write>(
+    val: &MapValue,
+    w: &mut W,
+    depth: u32,
+) -> Result<usize, MapError> {
+    let limit = MAX_CANON_BYTES.saturating_sub(CANON_HDR.len());
+    let mut counted = LimitedWriter {
+        inner: w,
+        written: 0,
+        limit,
+        exceeded: false,
+    };
+    match mcf_encode_to(val, depth, &mut counted) {
+        Ok(()) => Ok(counted.written),
+        Err(_) if counted.exceeded => Err(MapError::new(
+            ERR_LIMIT_SIZE,
+            "canon bytes exceed MAX_CANON_BYTES",
+        )),
+        Err(e) => Err(e),
+    }
+}
+
+/// Encode a canonical-model value, enforcing a [`StringProfile`] stricter
+/// than the default surrogate-only check.
+///
+/// Runs the profile check over the whole tree first (so a disallowed
+/// code point anywhere surfaces as `ERR_UTF8` before any bytes are
+/// produced), then delegates to `mcf_encode_value` for the rest.
+pub fn mcf_encode_value_with_profile(
+    val: &MapValue,
+    depth: u32,
+    profile: &StringProfile,
+) -> Result<Vec<u8>, MapError> {
+    validate_value_profile(val, profile)?;
+    mcf_encode_value(val, depth)
+}
+
 // TODO: #[inline] on hot encode paths — profile first to confirm
 // which paths actually benefit from inlining.
@@ -22,6 +22,19 @@ pub const ERR_KEY_ORDER: &str = "ERR_KEY_ORDER";
 pub const ERR_LIMIT_DEPTH: &str = "ERR_LIMIT_DEPTH";
 pub const ERR_LIMIT_SIZE: &str = "ERR_LIMIT_SIZE";
 
+/// Not one of the spec's 9 codes: surfaced when a `Write` sink (e.g. a
+/// socket or a hasher adapter) fails mid-encode. Deliberately excluded
+/// from `PRECEDENCE` since §6.2 only orders *data* violations, not I/O
+/// failures of the caller's own sink.
+pub const ERR_IO: &str = "ERR_IO";
+
+/// Not one of the spec's 9 codes: raised by the `schema` module's
+/// `MapSchema::validate` when a descriptor's runtime type at some path
+/// diverges from its declaration. Deliberately excluded from `PRECEDENCE`
+/// for the same reason as `ERR_IO` — schema validation is a v1.1 extension
+/// layered on top of §6's normative violations, not one of them.
+pub const ERR_SCHEMA_MISMATCH: &str = "ERR_SCHEMA_MISMATCH";
+
 /// Precedence order: index 0 wins.  This ordering is normative (§6.2).
 pub const PRECEDENCE: &[&str] = &[
     ERR_CANON_HDR,
@@ -39,10 +52,25 @@ pub const PRECEDENCE: &[&str] = &[
 ///
 /// The `code` field is one of the `ERR_*` constants and is what conformance
 /// tests compare against.  The `message` field is human-readable context.
+///
+/// `position` and `byte_offset` are best-effort localization, populated for
+/// parse-time failures that can point at a specific spot in the input (a
+/// `serde_json` parse error, a rejected surrogate escape) and left `None`
+/// everywhere else (e.g. container limit or schema violations, which
+/// describe the whole value rather than one input location). Neither
+/// field is part of the spec's error model (§6) — they exist so
+/// interactive/validation tooling can point a user at the exact location
+/// of a rejected float, null, or bad escape, without changing `code`.
 #[derive(Debug, Clone)]
 pub struct MapError {
     pub code: &'static str,
     pub message: String,
+    /// 1-based (line, column), when the failure can be localized there.
+    pub position: Option<(usize, usize)>,
+    /// Byte offset into the input, when derivable from `position` (it
+    /// isn't always — e.g. a streaming reader has no buffered text left
+    /// to re-measure line/column against).
+    pub byte_offset: Option<usize>,
 }
 
 impl MapError {
@@ -50,8 +78,18 @@ impl MapError {
         Self {
             code,
             message: message.into(),
+            position: None,
+            byte_offset: None,
         }
     }
+
+    /// Attach a source location to an already-built error. Doesn't change
+    /// `code` or `message` — only records where the problem was found.
+    pub fn with_position(mut self, line: usize, column: usize, byte_offset: Option<usize>) -> Self {
+        self.position = Some((line, column));
+        self.byte_offset = byte_offset;
+        self
+    }
 }
 
 impl fmt::Display for MapError {
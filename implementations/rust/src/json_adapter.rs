@@ -10,40 +10,60 @@
 //!   JSON float   → ERR_TYPE   (decimal point or exponent = rejected)
 //!   JSON null    → ERR_TYPE
 //!
-//! The trickiest part of this module is float vs integer detection.  With
-//! serde_json's `arbitrary_precision` feature, numbers arrive through the
-//! deserializer as a special map with key "$serde_json::private::Number"
-//! containing the raw JSON token string.  This lets us inspect for '.'
-//! and 'e'/'E' directly per §8.2.1.
+//! The trickiest part of this module is float vs integer detection.  Every
+//! value is first captured as a `serde_json::value::RawValue` (the public
+//! `raw_value` feature), whose `.get()` returns the exact JSON source text
+//! for whatever the deserializer is currently looking at — a number like
+//! "1e5" or "-0", but equally a whole object/array/string/bool/null.
+//! Sniffing that text's leading byte tells us which case we're in, and for
+//! numbers hands §8.2.1's '.'/'e'/'E' check the exact source characters,
+//! without the `arbitrary_precision` feature's trick of turning every
+//! number into a fake single-key map to smuggle the same text through a
+//! generic visitor.
 //!
 //! Duplicate key detection requires a custom deserialization strategy since
-//! serde_json's default Value type deduplicates keys silently.  Our custom
-//! Deserialize impl preserves all key-value pairs via visit_map.
-
-use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+//! serde_json's default Value type deduplicates keys silently.  Our
+//! `ObjectPairs` helper preserves all key-value pairs in source order.
+//!
+//! With the `simd` feature enabled, `json_strict_parse_with_dups` walks a
+//! `simd-json` tape instead (see `simd_adapter`), producing the same
+//! `ParsedJson` tree several times faster on AVX2 hosts — with one
+//! documented exception: `simd-json`'s tape has no arbitrary-precision
+//! number variant, so a JSON integer literal outside `i64`/`u64` range
+//! (e.g. `18446744073709551616`) tape-encodes as `Static::F64` rather than
+//! as an integer node, and is rejected as `ERR_TYPE` instead of being
+//! promoted to `MapValue::BigInt` the way the default `serde_json` +
+//! `RawValue` backend promotes it. See `simd_adapter`'s module doc for why
+//! this can't be bridged without re-deriving source digits `simd-json`
+//! doesn't retain. `serde_json` remains the default so the crate still
+//! builds on non-SIMD targets, and so BigInt-bearing documents keep
+//! producing a MID at all.
+
+use base64::Engine;
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
 use std::fmt;
 
+use crate::canonicalize::{canonicalize_value, DupPolicy};
 use crate::constants::*;
+use crate::encode::{ensure_sorted_unique, validate_utf8_scalar};
 use crate::errors::*;
 use crate::value::MapValue;
 
-// The magic key serde_json uses internally to pass raw number tokens
-// through serde's deserialization when arbitrary_precision is enabled.
-// This is a serde_json implementation detail, but it's stable and
-// well-documented in their codebase.
-const SERDE_JSON_NUMBER_KEY: &str = "$serde_json::private::Number";
-
 // ── Custom JSON value that preserves duplicate keys ────────────
 // serde_json::Value uses a BTreeMap/Map which deduplicates keys.  We need
 // to preserve all pairs to detect duplicates after escape resolution,
 // which serde has already done for us.
 
 #[derive(Debug)]
-enum ParsedJson {
+pub(crate) enum ParsedJson {
     Null,
     Bool(bool),
-    /// Raw number token string (preserved by arbitrary_precision feature).
-    /// Contains the exact JSON source token, e.g. "42", "3.14", "1e5".
+    /// Exact JSON source token, e.g. "42", "-0", "1e5" — captured via
+    /// `RawValue` rather than parsed into any numeric type, so the
+    /// '.'/'e'/'E' check in `json_to_canon_value` sees what the producer
+    /// actually wrote.
     Number(String),
     String(String),
     Array(Vec<ParsedJson>),
@@ -51,89 +71,83 @@ enum ParsedJson {
     Object(Vec<(String, ParsedJson)>),
 }
 
-struct ParsedJsonVisitor;
-
-impl<'de> Visitor<'de> for ParsedJsonVisitor {
-    type Value = ParsedJson;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("any JSON value")
-    }
-
-    fn visit_bool<E: de::Error>(self, v: bool) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::Bool(v))
-    }
-
-    // Fallback numeric visitors — with arbitrary_precision these normally
-    // won't fire (numbers route through visit_map with the magic key),
-    // but we handle them defensively.
-    fn visit_i64<E: de::Error>(self, v: i64) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::Number(v.to_string()))
-    }
-
-    fn visit_u64<E: de::Error>(self, v: u64) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::Number(v.to_string()))
-    }
-
-    fn visit_f64<E: de::Error>(self, v: f64) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::Number(v.to_string()))
+impl<'de> Deserialize<'de> for ParsedJson {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Box::<RawValue>::deserialize(deserializer)?;
+        parsed_from_raw(&raw).map_err(|e| de::Error::custom(e.to_string()))
     }
+}
 
-    fn visit_str<E: de::Error>(self, v: &str) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::String(v.to_string()))
+/// Dispatch on the leading byte of `raw`'s source text. Objects and arrays
+/// recurse by re-capturing each child as its own `RawValue` rather than
+/// going through `ParsedJson::deserialize` again (which would just ask
+/// for another `RawValue` covering the same text and loop forever).
+fn parsed_from_raw(raw: &RawValue) -> Result<ParsedJson, MapError> {
+    let text = raw.get();
+    let trimmed = text.trim_start();
+    let parse_err =
+        |e: serde_json::Error| MapError::new(ERR_CANON_MCF, format!("JSON parse error: {}", e));
+
+    match trimmed.as_bytes().first() {
+        Some(b'"') => {
+            let s: String = serde_json::from_str(text).map_err(parse_err)?;
+            ensure_no_surrogates(&s)?;
+            Ok(ParsedJson::String(s))
+        }
+        Some(b't') | Some(b'f') => {
+            let b: bool = serde_json::from_str(text).map_err(parse_err)?;
+            Ok(ParsedJson::Bool(b))
+        }
+        Some(b'n') => Ok(ParsedJson::Null),
+        Some(b'[') => {
+            let items: Vec<Box<RawValue>> = serde_json::from_str(text).map_err(parse_err)?;
+            let mut out = Vec::with_capacity(items.len());
+            for item in &items {
+                out.push(parsed_from_raw(item)?);
+            }
+            Ok(ParsedJson::Array(out))
+        }
+        Some(b'{') => {
+            let ObjectPairs(pairs) = serde_json::from_str(text).map_err(parse_err)?;
+            let mut out = Vec::with_capacity(pairs.len());
+            for (key, raw) in &pairs {
+                out.push((key.clone(), parsed_from_raw(raw)?));
+            }
+            Ok(ParsedJson::Object(out))
+        }
+        // Anything else is a number (a digit or leading '-') — keep its
+        // exact source text instead of routing it through any numeric type.
+        _ => Ok(ParsedJson::Number(trimmed.trim_end().to_string())),
     }
+}
 
-    fn visit_string<E: de::Error>(self, v: String) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::String(v))
-    }
+/// Collects a JSON object's key/value pairs in source order, preserving
+/// duplicates, with each value left as a `RawValue` for `parsed_from_raw`
+/// to recurse into.
+struct ObjectPairs(Vec<(String, Box<RawValue>)>);
 
-    fn visit_unit<E: de::Error>(self) -> Result<ParsedJson, E> {
-        Ok(ParsedJson::Null)
-    }
+impl<'de> Deserialize<'de> for ObjectPairs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ObjectPairsVisitor;
 
-    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ParsedJson, A::Error> {
-        let mut items = Vec::new();
-        while let Some(item) = seq.next_element::<ParsedJson>()? {
-            items.push(item);
-        }
-        Ok(ParsedJson::Array(items))
-    }
+        impl<'de> Visitor<'de> for ObjectPairsVisitor {
+            type Value = ObjectPairs;
 
-    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ParsedJson, A::Error> {
-        // With serde_json's arbitrary_precision feature, numbers are routed
-        // through visit_map as: {"$serde_json::private::Number": "raw_token"}.
-        // We detect this by checking the first key.
-        let first_key: Option<String> = map.next_key()?;
-
-        match first_key {
-            Some(ref key) if key == SERDE_JSON_NUMBER_KEY => {
-                // This is a raw number token from serde_json's arbitrary_precision
-                let raw: String = map.next_value()?;
-                Ok(ParsedJson::Number(raw))
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
             }
-            Some(first_key) => {
-                // Regular JSON object — collect all pairs including duplicates
-                let mut pairs = Vec::new();
-                let first_value: ParsedJson = map.next_value()?;
-                pairs.push((first_key, first_value));
 
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ObjectPairs, A::Error> {
+                let mut pairs = Vec::new();
                 while let Some(key) = map.next_key::<String>()? {
-                    let value: ParsedJson = map.next_value()?;
+                    let value: Box<RawValue> = map.next_value()?;
                     pairs.push((key, value));
                 }
-                Ok(ParsedJson::Object(pairs))
-            }
-            None => {
-                // Empty object {}
-                Ok(ParsedJson::Object(Vec::new()))
+                Ok(ObjectPairs(pairs))
             }
         }
-    }
-}
 
-impl<'de> Deserialize<'de> for ParsedJson {
-    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        deserializer.deserialize_any(ParsedJsonVisitor)
+        deserializer.deserialize_map(ObjectPairsVisitor)
     }
 }
 
@@ -142,7 +156,7 @@ impl<'de> Deserialize<'de> for ParsedJson {
 // so in practice this check is defense-in-depth.  serde_json correctly
 // rejects lone surrogates during parsing as well.
 
-fn ensure_no_surrogates(s: &str) -> Result<(), MapError> {
+pub(crate) fn ensure_no_surrogates(s: &str) -> Result<(), MapError> {
     for ch in s.chars() {
         let cp = ch as u32;
         if (0xD800..=0xDFFF).contains(&cp) {
@@ -162,11 +176,22 @@ fn ensure_no_surrogates(s: &str) -> Result<(), MapError> {
 /// guarantees the spec-required ERR_UTF8 code regardless of serde version.
 /// A high surrogate followed by a low surrogate is still rejected — JSON
 /// text is UTF-8, and surrogates are only meaningful in UTF-16.
+///
+/// Tracks line/byte-offset as it scans so a rejection can report exactly
+/// where the offending `\uD8xx` escape starts; `column` is a byte count
+/// from the start of the line, not a char count, since this is a raw-byte
+/// scan rather than a `chars()` walk.
 fn scan_for_surrogate_escapes(raw: &[u8]) -> Result<(), MapError> {
     let mut in_string = false;
     let mut i = 0;
+    let mut line = 1usize;
+    let mut line_start = 0usize;
     while i < raw.len() {
         let b = raw[i];
+        if b == b'\n' {
+            line += 1;
+            line_start = i + 1;
+        }
         if !in_string {
             if b == b'"' {
                 in_string = true;
@@ -176,6 +201,7 @@ fn scan_for_surrogate_escapes(raw: &[u8]) -> Result<(), MapError> {
         }
         // Inside a string.
         if b == b'\\' {
+            let esc_start = i;
             i += 1;
             if i >= raw.len() {
                 break;
@@ -187,7 +213,8 @@ fn scan_for_surrogate_escapes(raw: &[u8]) -> Result<(), MapError> {
                             return Err(MapError::new(
                                 ERR_UTF8,
                                 format!("surrogate escape \\u{}", hex),
-                            ));
+                            )
+                            .with_position(line, esc_start - line_start + 1, Some(esc_start)));
                         }
                     }
                 }
@@ -205,16 +232,35 @@ fn scan_for_surrogate_escapes(raw: &[u8]) -> Result<(), MapError> {
     Ok(())
 }
 
+/// Best-effort byte offset for a 1-based (line, column) pair — such as
+/// `serde_json::Error::line`/`::column` — by re-measuring against buffered
+/// source text. `None` when `line` is out of range for `text` (it always
+/// is for errors surfaced from a streaming reader, which has no buffered
+/// text left to re-measure against).
+fn byte_offset_for(text: &str, line: usize, column: usize) -> Option<usize> {
+    let mut offset = 0usize;
+    for (i, l) in text.split_inclusive('\n').enumerate() {
+        if i + 1 == line {
+            let col_offset: usize = l
+                .chars()
+                .take(column.saturating_sub(1))
+                .map(|c| c.len_utf8())
+                .sum();
+            return Some(offset + col_offset);
+        }
+        offset += l.len();
+    }
+    None
+}
+
 // ── JSON parse with BOM and duplicate detection ────────────────
 
-/// Parse raw JSON bytes under JSON-STRICT rules.
-///
-/// Returns `(parsed_value, dup_found)`.  Duplicate detection does NOT
-/// short-circuit — we record the flag and keep parsing so that
-/// higher-precedence errors (ERR_TYPE from null, ERR_UTF8 from bad
-/// encoding) can still surface.  The caller raises ERR_DUP_KEY only
-/// if no higher-precedence error occurred.
-fn json_strict_parse_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapError> {
+/// Shared prechecks that apply to raw JSON bytes regardless of which
+/// parser backend (`serde_json` or, with the `simd` feature, `simd-json`)
+/// ends up walking them: size limit, BOM rejection, UTF-8 validity, and
+/// the surrogate-escape pre-scan. Returns the validated `&str` view of
+/// `raw` for backends that want it.
+fn prescan_json_bytes(raw: &[u8]) -> Result<&str, MapError> {
     if raw.len() > MAX_CANON_BYTES {
         return Err(MapError::new(
             ERR_LIMIT_SIZE,
@@ -243,10 +289,34 @@ fn json_strict_parse_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapErro
     // Same approach as the Go implementation's scanForSurrogateEscapes.
     scan_for_surrogate_escapes(raw)?;
 
+    Ok(text)
+}
+
+/// Parse raw JSON bytes under JSON-STRICT rules.
+///
+/// Returns `(parsed_value, dup_found)`.  Duplicate detection does NOT
+/// short-circuit — we record the flag and keep parsing so that
+/// higher-precedence errors (ERR_TYPE from null, ERR_UTF8 from bad
+/// encoding) can still surface.  The caller raises ERR_DUP_KEY only
+/// if no higher-precedence error occurred.
+///
+/// Two backends produce `ParsedJson` from here: the default `serde_json`
+/// path below, and (behind the `simd` feature) `simd_adapter`'s tape walk.
+/// Both run the same `prescan_json_bytes` checks and the same
+/// `check_duplicates` pass, and agree byte-for-byte on every CANON_BYTES
+/// output for any document whose integers fit in `i64`/`u64` — the feature
+/// only changes how fast we get from JSON text to `ParsedJson` there.
+/// Outside that range the two backends intentionally diverge: see the
+/// module doc above and `simd_adapter`'s doc comment.
+#[cfg(not(feature = "simd"))]
+fn json_strict_parse_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapError> {
+    let text = prescan_json_bytes(raw)?;
+
     // Parse with our custom type that preserves duplicate keys.
-    // serde_json with arbitrary_precision preserves raw number tokens.
-    let parsed: ParsedJson = serde_json::from_str(text).map_err(|_| {
-        MapError::new(ERR_CANON_MCF, "JSON parse error")
+    let parsed: ParsedJson = serde_json::from_str(text).map_err(|e| {
+        let (line, column) = (e.line(), e.column());
+        MapError::new(ERR_CANON_MCF, format!("JSON parse error: {}", e))
+            .with_position(line, column, byte_offset_for(text, line, column))
     })?;
 
     // Detect duplicates by scanning all Object nodes.
@@ -258,9 +328,15 @@ fn json_strict_parse_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapErro
     Ok((parsed, dup_found))
 }
 
+#[cfg(feature = "simd")]
+fn json_strict_parse_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapError> {
+    prescan_json_bytes(raw)?;
+    crate::simd_adapter::parse_tape_with_dups(raw)
+}
+
 /// Recursively check for duplicate keys in all objects and validate
 /// string surrogate freedom.
-fn check_duplicates(val: &ParsedJson, dup_found: &mut bool) -> Result<(), MapError> {
+pub(crate) fn check_duplicates(val: &ParsedJson, dup_found: &mut bool) -> Result<(), MapError> {
     match val {
         ParsedJson::Object(pairs) => {
             let mut seen = std::collections::HashSet::new();
@@ -354,20 +430,80 @@ fn json_to_canon_value(x: &ParsedJson, depth: u32) -> Result<MapValue, MapError>
                 ));
             }
 
-            // Parse as integer and range-check against i64 bounds.
-            // Use i128 to detect overflow without panicking.
-            let val: i128 = token.parse().map_err(|_| {
-                MapError::new(ERR_TYPE, format!("invalid integer: {}", token))
-            })?;
+            // Integers that fit in i64 use the existing small-int tag;
+            // anything bigger (token amounts, large IDs, crypto values)
+            // promotes to BigInt instead of being rejected as ERR_TYPE.
+            match token.parse::<i64>() {
+                Ok(val) => Ok(MapValue::Integer(val)),
+                Err(_) => MapValue::big_int_from_decimal(token).ok_or_else(|| {
+                    MapError::new(ERR_TYPE, format!("invalid integer: {}", token))
+                }),
+            }
+        }
+    }
+}
+
+/// Convert a parsed JSON value to a MapValue without deduping or sorting
+/// object keys — entries are kept exactly as written, duplicates and all.
+///
+/// Counterpart to `json_to_canon_value` for the normalized ingestion path
+/// (see `parse_json_normalized`): the tree this produces is not yet
+/// canonical and must be run through `canonicalize_value` before encoding.
+fn json_to_raw_value(x: &ParsedJson, depth: u32) -> Result<MapValue, MapError> {
+    if depth > MAX_DEPTH {
+        return Err(MapError::new(ERR_LIMIT_DEPTH, "exceeds MAX_DEPTH"));
+    }
+
+    match x {
+        ParsedJson::Object(pairs) => {
+            let mut entries: Vec<(String, MapValue)> = Vec::with_capacity(pairs.len());
+            for (key, val) in pairs {
+                ensure_no_surrogates(key)?;
+                let child_depth = match val {
+                    ParsedJson::Object(_) | ParsedJson::Array(_) => depth + 1,
+                    _ => depth,
+                };
+                let child = json_to_raw_value(val, child_depth)?;
+                entries.push((key.clone(), child));
+            }
+            Ok(MapValue::Map(entries))
+        }
+
+        ParsedJson::Array(items) => {
+            let mut result = Vec::with_capacity(items.len());
+            for item in items {
+                let child_depth = match item {
+                    ParsedJson::Object(_) | ParsedJson::Array(_) => depth + 1,
+                    _ => depth,
+                };
+                result.push(json_to_raw_value(item, child_depth)?);
+            }
+            Ok(MapValue::List(result))
+        }
+
+        ParsedJson::String(s) => {
+            ensure_no_surrogates(s)?;
+            Ok(MapValue::String(s.clone()))
+        }
+
+        ParsedJson::Bool(b) => Ok(MapValue::Boolean(*b)),
+
+        ParsedJson::Null => Err(MapError::new(ERR_TYPE, "JSON null not allowed")),
 
-            if val < i64::MIN as i128 || val > i64::MAX as i128 {
+        ParsedJson::Number(token) => {
+            if token.contains('.') || token.contains('e') || token.contains('E') {
                 return Err(MapError::new(
                     ERR_TYPE,
-                    format!("integer overflow: {}", token),
+                    format!("JSON float not allowed: {}", token),
                 ));
             }
 
-            Ok(MapValue::Integer(val as i64))
+            match token.parse::<i64>() {
+                Ok(val) => Ok(MapValue::Integer(val)),
+                Err(_) => MapValue::big_int_from_decimal(token).ok_or_else(|| {
+                    MapError::new(ERR_TYPE, format!("invalid integer: {}", token))
+                }),
+            }
         }
     }
 }
@@ -385,3 +521,431 @@ pub fn parse_json_strict(raw: &[u8]) -> Result<(MapValue, bool), MapError> {
     let val = json_to_canon_value(&parsed, 1)?;
     Ok((val, dup_found))
 }
+
+/// Parse raw UTF-8 JSON bytes like `parse_json_strict`, but instead of
+/// immediately deduping (first-wins) and sorting object keys, preserve
+/// them exactly as written and hand the whole tree to `canonicalize_value`
+/// with the caller's chosen `DupPolicy`.
+///
+/// This is the ingestion path for `mid_full_json_normalized` /
+/// `canonical_bytes_normalized` — it tolerates unsorted and (depending on
+/// `policy`) duplicate-keyed JSON that `parse_json_strict`'s hardcoded
+/// first-wins-then-ERR_DUP_KEY behavior would reject.
+pub fn parse_json_normalized(raw: &[u8], policy: DupPolicy) -> Result<MapValue, MapError> {
+    let (parsed, _dup_found) = json_strict_parse_with_dups(raw)?;
+    let raw_val = json_to_raw_value(&parsed, 1)?;
+    canonicalize_value(raw_val, policy)
+}
+
+// ── JSON bridge: build MCF bytes directly from a streaming JSON tokenizer ──
+//
+// Unlike `parse_json_strict`, this never builds a `ParsedJson`/`MapValue`
+// tree: `serde_json`'s own tokenizer calls straight into a `Visitor` that
+// returns each value's already-encoded MCF bytes, assembled bottom-up as
+// tokens arrive.
+//
+// This can't drive `MidHasher` (see that module's own doc comment):
+// `begin_map`/`begin_list` need a container's entry count up front so
+// they can write the tag+count header before any child, but JSON has no
+// length prefix of its own — `serde_json`'s `SeqAccess`/`MapAccess` only
+// reveal how many entries a container had once it's fully drained. So
+// each container's children are assembled here in memory before its
+// header can be written; for a document whose root is an object or array
+// (effectively all of them), that means peak memory ends up proportional
+// to the whole document, the same as `mid_full_json`'s `MapValue`-tree
+// path. What this bridge actually buys over `mid_full_json`: it reads
+// incrementally from any `io::Read` (a socket, a pipe, a file far larger
+// than the caller wants to hold as one `&[u8]`) and never materializes an
+// intermediate `MapValue` tree on top of the bytes it produces.
+//
+// Behavioral differences from `mid_full_json`: key order is enforced
+// incrementally rather than by sorting, so object keys in the source
+// JSON must already be in canonical (raw UTF-8 byte) order — an
+// out-of-order key surfaces as `ERR_KEY_ORDER` here where `mid_full_json`
+// would have silently sorted it. No integer is promoted to BigInt, and
+// every rejection that isn't one of this visitor's own `MapError`s (a
+// null, a float, an out-of-range integer, a depth or entry-count
+// overrun, malformed JSON) surfaces as `ERR_CANON_MCF`.
+
+struct McfEventVisitor {
+    depth: u32,
+}
+
+impl<'de> Visitor<'de> for McfEventVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Vec<u8>, E> {
+        Ok(vec![TAG_BOOLEAN, if v { 0x01 } else { 0x00 }])
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Vec<u8>, E> {
+        let mut out = Vec::with_capacity(9);
+        out.push(TAG_INTEGER);
+        out.extend_from_slice(&v.to_be_bytes());
+        Ok(out)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Vec<u8>, E> {
+        let v = i64::try_from(v).map_err(|_| de::Error::custom("integer overflow"))?;
+        self.visit_i64(v)
+    }
+
+    fn visit_f64<E: de::Error>(self, _v: f64) -> Result<Vec<u8>, E> {
+        Err(de::Error::custom("JSON float not allowed"))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<u8>, E> {
+        validate_utf8_scalar(v).map_err(de::Error::custom)?;
+        let raw = v.as_bytes();
+        let mut out = Vec::with_capacity(5 + raw.len());
+        out.push(TAG_STRING);
+        out.extend_from_slice(&(raw.len() as u32).to_be_bytes());
+        out.extend_from_slice(raw);
+        Ok(out)
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Vec<u8>, E> {
+        self.visit_str(&v)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Vec<u8>, E> {
+        Err(de::Error::custom("JSON null not allowed"))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        if self.depth + 1 > MAX_DEPTH {
+            return Err(de::Error::custom("depth exceeds MAX_DEPTH"));
+        }
+        let mut items: Vec<Vec<u8>> = Vec::new();
+        while let Some(item) =
+            seq.next_element_seed(McfEventSeed { depth: self.depth + 1 })?
+        {
+            items.push(item);
+        }
+        if items.len() > MAX_LIST_ENTRIES as usize {
+            return Err(de::Error::custom("list entry count exceeds limit"));
+        }
+        let mut out = Vec::new();
+        out.push(TAG_LIST);
+        out.extend_from_slice(&(items.len() as u32).to_be_bytes());
+        for item in items {
+            out.extend_from_slice(&item);
+        }
+        Ok(out)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Vec<u8>, A::Error> {
+        if self.depth + 1 > MAX_DEPTH {
+            return Err(de::Error::custom("depth exceeds MAX_DEPTH"));
+        }
+        let mut entries: Vec<(String, Vec<u8>)> = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            validate_utf8_scalar(&key).map_err(de::Error::custom)?;
+            let value = map.next_value_seed(McfEventSeed { depth: self.depth + 1 })?;
+            entries.push((key, value));
+        }
+        if entries.len() > MAX_MAP_ENTRIES as usize {
+            return Err(de::Error::custom("map entry count exceeds limit"));
+        }
+        let key_bytes: Vec<&[u8]> = entries.iter().map(|(k, _)| k.as_bytes()).collect();
+        ensure_sorted_unique(&key_bytes).map_err(de::Error::custom)?;
+
+        let mut out = Vec::new();
+        out.push(TAG_MAP);
+        out.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for (k, v) in &entries {
+            let raw = k.as_bytes();
+            out.push(TAG_STRING);
+            out.extend_from_slice(&(raw.len() as u32).to_be_bytes());
+            out.extend_from_slice(raw);
+            out.extend_from_slice(v);
+        }
+        Ok(out)
+    }
+}
+
+struct McfEventSeed {
+    depth: u32,
+}
+
+impl<'de> DeserializeSeed<'de> for McfEventSeed {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_any(McfEventVisitor { depth: self.depth })
+    }
+}
+
+/// Compute a MID from a streaming `io::Read` of JSON text, without ever
+/// building a `MapValue` tree.
+///
+/// Drives the MCF encoding directly from `serde_json`'s own tokenizer and
+/// hashes the result in one shot — see the module-level note just above
+/// for why this can't be bounded by nesting depth the way `MidHasher`
+/// itself is, and what reading incrementally from `r` still buys here.
+pub fn mid_full_json_reader<R: std::io::Read>(r: R) -> Result<String, MapError> {
+    let mut de = serde_json::Deserializer::from_reader(r);
+    let body = de
+        .deserialize_any(McfEventVisitor { depth: 0 })
+        .map_err(|e| {
+            // The visitor's own errors come back through serde as
+            // "<CODE>: <message>"; keep that code.
+            let msg = e.to_string();
+            let code = PRECEDENCE
+                .iter()
+                .copied()
+                .find(|c| msg.starts_with(&format!("{}:", c)))
+                .unwrap_or(ERR_CANON_MCF);
+            MapError::new(code, format!("JSON parse error: {}", e))
+                .with_position(e.line(), e.column(), None)
+        })?;
+    if CANON_HDR.len() + body.len() > MAX_CANON_BYTES {
+        return Err(MapError::new(ERR_LIMIT_SIZE, "canon bytes exceed MAX_CANON_BYTES"));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(CANON_HDR);
+    hasher.update(&body);
+    let digest = hasher.finalize();
+    Ok(format!(
+        "map1:{}",
+        digest.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    ))
+}
+
+// ── Streaming JSON-STRICT parser (arbitrary io::Read) ────────────────
+//
+// `parse_json_strict` requires the whole input as a `&[u8]` up front and
+// only checks MAX_CANON_BYTES once that buffer already exists. This is
+// the same `ParsedJson` dup-preserving tree, but fed
+// from an `io::Read` so callers don't have to materialize a socket/pipe
+// into memory twice (once to read it, once for us to re-buffer it).
+
+/// A marker message `LimitedReader` puts in the `io::Error` it raises so
+/// the caller can tell "input too large" apart from a genuine I/O failure
+/// or malformed JSON, both of which also surface as `io::Error` through
+/// `serde_json`.
+const LIMIT_EXCEEDED_MARKER: &str = "map1: input exceeds MAX_CANON_BYTES";
+
+/// Wraps a reader and fails with an `io::Error` carrying
+/// `LIMIT_EXCEEDED_MARKER` the moment total bytes read exceed
+/// `MAX_CANON_BYTES`, so an oversized stream is rejected while still
+/// streaming in rather than after being fully buffered.
+struct LimitedReader<R> {
+    inner: R,
+    remaining: usize,
+}
+
+impl<R: std::io::Read> std::io::Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        match self.remaining.checked_sub(n) {
+            Some(rest) => {
+                self.remaining = rest;
+                Ok(n)
+            }
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                LIMIT_EXCEEDED_MARKER,
+            )),
+        }
+    }
+}
+
+/// Parse a streaming `io::Read` of JSON text under JSON-STRICT rules,
+/// without requiring the whole input as a `&[u8]` slice first.
+///
+/// Built on `serde_json::Deserializer::from_reader` with the same
+/// dup-preserving `ParsedJson` tree `parse_json_strict` uses — only how
+/// bytes arrive differs. `MAX_CANON_BYTES` is enforced
+/// incrementally via `LimitedReader` rather than only after the whole
+/// document has been buffered.
+///
+/// Returns `(canonical_value, dup_found)`, same contract as
+/// `parse_json_strict`.
+///
+/// Caveat: `parse_json_strict` pre-scans raw bytes for `\uD800`-`\uDFFF`
+/// escape sequences so it can report them as `ERR_UTF8` (§8.1); that scan
+/// needs random access to the whole input, which a stream doesn't offer.
+/// Here, a malformed surrogate escape is instead caught by `serde_json`
+/// itself during decoding and surfaces as `ERR_CANON_MCF`, not `ERR_UTF8`.
+pub fn parse_json_strict_reader<R: std::io::Read>(r: R) -> Result<(MapValue, bool), MapError> {
+    let limited = LimitedReader {
+        inner: r,
+        remaining: MAX_CANON_BYTES,
+    };
+    let mut de = serde_json::Deserializer::from_reader(limited);
+    let parsed = ParsedJson::deserialize(&mut de).map_err(|e| {
+        if e.to_string().contains(LIMIT_EXCEEDED_MARKER) {
+            MapError::new(ERR_LIMIT_SIZE, "input exceeds MAX_CANON_BYTES")
+        } else {
+            // No buffered text to re-measure against here, so byte_offset
+            // stays None — only (line, column) is derivable from a reader.
+            MapError::new(ERR_CANON_MCF, format!("JSON parse error: {}", e))
+                .with_position(e.line(), e.column(), None)
+        }
+    })?;
+
+    let mut dup_found = false;
+    check_duplicates(&parsed, &mut dup_found)?;
+    let val = json_to_canon_value(&parsed, 1)?;
+    Ok((val, dup_found))
+}
+
+// ── NDJSON / multi-descriptor stream ──────────────────────────
+//
+// Batch tools fingerprinting e.g. an audit log want one MID per line
+// without re-parsing the whole file per line themselves. This drives
+// `serde_json`'s own `StreamDeserializer` — the same machinery behind
+// `serde_json::Deserializer::from_reader(...).into_iter()` — over
+// back-to-back JSON values (NDJSON is exactly that: no array wrapper, no
+// separators beyond whitespace).
+
+/// Iterator backing `lib::mid_stream`: drives
+/// `serde_json::Deserializer::into_iter::<ParsedJson>()` over
+/// newline-delimited JSON, yielding one MID result per document.
+pub(crate) struct MidStream<R> {
+    inner: serde_json::StreamDeserializer<'static, serde_json::de::IoRead<R>, ParsedJson>,
+}
+
+impl<R: std::io::Read> MidStream<R> {
+    pub(crate) fn new(r: R) -> Self {
+        Self {
+            inner: serde_json::Deserializer::from_reader(r).into_iter::<ParsedJson>(),
+        }
+    }
+}
+
+impl<R: std::io::Read> Iterator for MidStream<R> {
+    type Item = Result<String, MapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parsed = match self.inner.next()? {
+            Ok(p) => p,
+            Err(e) => {
+                return Some(Err(MapError::new(
+                    ERR_CANON_MCF,
+                    format!("JSON parse error: {}", e),
+                )
+                .with_position(e.line(), e.column(), None)));
+            }
+        };
+        Some(mid_from_record(&parsed))
+    }
+}
+
+/// Route one NDJSON record through the same pipeline `mid_full_json`
+/// uses: build the canonical `MapValue` (sorting/deduping exactly like
+/// the slice-based path), compute its MID, and only then raise
+/// `ERR_DUP_KEY` if a duplicate key was seen — same precedence order as
+/// `mid_full_json`.
+///
+/// `MAX_CANON_BYTES` is enforced per record (inside `mid_from_value`),
+/// not across the whole stream, and a malformed record returns its own
+/// `Err` without otherwise disturbing the iterator — the caller decides
+/// whether to abort or skip past it.
+fn mid_from_record(parsed: &ParsedJson) -> Result<String, MapError> {
+    let mut dup_found = false;
+    check_duplicates(parsed, &mut dup_found)?;
+    let val = json_to_canon_value(parsed, 1)?;
+    let mid = crate::mid::mid_from_value(&val)?;
+    if dup_found {
+        return Err(MapError::new(ERR_DUP_KEY, "duplicate key in JSON"));
+    }
+    Ok(mid)
+}
+
+// ── Canonical model → JSON (inverse of JSON-STRICT) ──────────────────
+//
+// The reverse direction of this module: given a `MapValue`, emit a
+// RFC 8785-style canonical JSON rendering of it — sorted keys (already
+// guaranteed by `MapValue::Map`'s invariant), no insignificant whitespace,
+// integers printed as bare digits, booleans as `true`/`false`. Feeding
+// this output back through `parse_json_strict` reproduces the identical
+// MID for STRING, LIST, MAP, BOOLEAN, and INTEGER.
+//
+// BYTES is the one asymmetry: JSON has no byte-string type, so a BYTES
+// value is emitted as a JSON string carrying `BYTES_JSON_SENTINEL`
+// followed by unpadded base64url. Reading that string back through
+// `parse_json_strict` produces a STRING holding the sentinel-prefixed
+// text, not the original BYTES — there's no way around that without a
+// JSON-STRICT extension to recognize the sentinel on ingestion, which is
+// out of scope here. Emitted JSON containing the sentinel is meant for
+// human inspection / diffing, not as a guaranteed round-trip format.
+
+/// Prefix marking a JSON string emitted by `canon_value_to_json` as the
+/// rendering of a `MapValue::Bytes`, followed by unpadded base64url. Purely
+/// a human-inspection convention — `parse_json_strict` does not recognize
+/// it, so re-ingesting this string yields STRING, not the original BYTES.
+pub const BYTES_JSON_SENTINEL: &str = "map1:bytes:base64url:";
+
+/// Render `value` as canonical (RFC 8785-style) JSON text.
+///
+/// Key order and uniqueness are taken as given — this does not re-sort or
+/// re-validate a `MapValue::Map`, it assumes the canonical invariants the
+/// rest of this crate already enforces before encoding/hashing it.
+pub fn canon_value_to_json(value: &MapValue) -> String {
+    let mut out = String::new();
+    write_json_value(&mut out, value);
+    out
+}
+
+fn write_json_value(out: &mut String, value: &MapValue) {
+    match value {
+        MapValue::String(s) => write_json_string(out, s),
+        MapValue::Bytes(b) => {
+            let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(b);
+            write_json_string(out, &format!("{}{}", BYTES_JSON_SENTINEL, encoded));
+        }
+        MapValue::List(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_value(out, item);
+            }
+            out.push(']');
+        }
+        MapValue::Map(entries) => {
+            out.push('{');
+            for (i, (key, val)) in entries.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_json_string(out, key);
+                out.push(':');
+                write_json_value(out, val);
+            }
+            out.push('}');
+        }
+        MapValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+        MapValue::Integer(n) => out.push_str(&n.to_string()),
+        MapValue::BigInt(..) => out.push_str(&value.to_string()),
+    }
+}
+
+/// Write `s` as a quoted JSON string, escaping only what RFC 8259 requires
+/// (`"`, `\`, and control characters below U+0020) — non-ASCII code points
+/// are emitted as literal UTF-8 rather than `\uXXXX`, matching RFC 8785's
+/// "shortest valid escape" canonical form.
+fn write_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
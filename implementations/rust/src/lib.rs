@@ -17,18 +17,41 @@
 //! v1.1 adds BOOLEAN and INTEGER types.  Booleans and integers are now
 //! distinct from their string representations.
 
+pub mod canonicalize;
 pub mod constants;
 pub mod decode;
 pub mod encode;
 pub mod errors;
 pub mod json_adapter;
 pub mod mid;
+pub mod mid_hasher;
+pub mod mmap_validate;
+pub mod multihash;
 pub mod projection;
+pub mod raw_canon;
+pub mod schema;
+pub mod serde_bridge;
+#[cfg(feature = "simd")]
+mod simd_adapter;
+pub mod stream_decode;
+pub mod string_profile;
 pub mod value;
 
 pub use constants::SPEC_VERSION;
-pub use errors::{MapError, ERR_CANON_HDR, ERR_CANON_MCF, ERR_DUP_KEY, ERR_KEY_ORDER,
-                 ERR_LIMIT_DEPTH, ERR_LIMIT_SIZE, ERR_SCHEMA, ERR_TYPE, ERR_UTF8};
+pub use errors::{MapError, ERR_CANON_HDR, ERR_CANON_MCF, ERR_DUP_KEY, ERR_IO, ERR_KEY_ORDER,
+                 ERR_LIMIT_DEPTH, ERR_LIMIT_SIZE, ERR_SCHEMA, ERR_SCHEMA_MISMATCH, ERR_TYPE,
+                 ERR_UTF8};
+pub use canonicalize::{canonicalize_map, DupPolicy};
+pub use encode::mcf_encode_value_with_profile;
+pub use json_adapter::{canon_value_to_json, mid_full_json_reader, BYTES_JSON_SENTINEL};
+pub use mid_hasher::MidHasher;
+pub use mmap_validate::{mid_from_canon_mmap, CanonMmap};
+pub use multihash::{mid_multihash, parse_mid, MidAlgo, ParsedMid};
+pub use raw_canon::RawCanon;
+pub use schema::{MapSchema, SchemaType};
+pub use serde_bridge::{from_map_value, mid_of, to_map_value};
+pub use stream_decode::{Decode, McfDecoder};
+pub use string_profile::StringProfile;
 pub use value::MapValue;
 
 use json_adapter::parse_json_strict;
@@ -46,6 +69,16 @@ pub fn mid_full(descriptor: &MapValue) -> Result<String, MapError> {
     mid_from_value(&val)
 }
 
+/// Compute a MID over the full descriptor the same way `mid_full` does,
+/// but via a streaming encode that never materializes CANON_BYTES — see
+/// `mid::mid_from_value_streaming`. Produces exactly the same MID as
+/// `mid_full` for any input that fits in memory; this is for descriptors
+/// that don't.
+pub fn mid_full_streaming(descriptor: &MapValue) -> Result<String, MapError> {
+    let val = full_project(descriptor);
+    mid::mid_from_value_streaming(&val)
+}
+
 /// Compute a MID over selected fields (BIND projection).
 ///
 /// Pointers are RFC 6901 JSON Pointer strings (e.g., "/action", "/config/port").
@@ -54,6 +87,19 @@ pub fn mid_bind(descriptor: &MapValue, pointers: &[&str]) -> Result<String, MapE
     mid_from_value(&val)
 }
 
+/// Compute a MID over the full descriptor, after validating it against
+/// `schema` (FULL projection, schema-checked).
+///
+/// Runs `MapSchema::validate` before encoding/hashing, so a descriptor
+/// whose runtime types diverge from the schema's declarations raises
+/// `ERR_SCHEMA_MISMATCH` instead of silently producing a MID that a
+/// type-confused producer (e.g. `String("true")` where `Boolean` was
+/// expected) happened to get past the encoder.
+pub fn mid_full_checked(descriptor: &MapValue, schema: &schema::MapSchema) -> Result<String, MapError> {
+    schema.validate(descriptor)?;
+    mid_full(descriptor)
+}
+
 /// Return CANON_BYTES (header + MCF) for the full descriptor.
 pub fn canonical_bytes_full(descriptor: &MapValue) -> Result<Vec<u8>, MapError> {
     let val = full_project(descriptor);
@@ -93,11 +139,77 @@ pub fn mid_bind_json(raw: &[u8], pointers: &[&str]) -> Result<String, MapError>
     Ok(format!("map1:{}", sha256_hex(&canon)))
 }
 
+/// Compute a MID from a streaming `io::Read` of JSON text (JSON-STRICT +
+/// FULL), building the full `MapValue` tree via
+/// `json_adapter::parse_json_strict_reader` rather than assembling MCF
+/// bytes straight from the tokenizer.
+///
+/// Named distinctly from the existing `mid_full_json_reader` (which,
+/// per its own docs, requires source keys already in canonical order):
+/// this path tolerates any source key order, exactly like
+/// `mid_full_json`, at the cost of buffering the parsed tree instead of
+/// staying tokenizer-driven.
+pub fn mid_full_json_strict_reader<R: std::io::Read>(r: R) -> Result<String, MapError> {
+    let (val, dup_found) = json_adapter::parse_json_strict_reader(r)?;
+    let canon = canon_bytes_from_value(&val)?;
+    if dup_found {
+        return Err(MapError::new(ERR_DUP_KEY, "duplicate key in JSON"));
+    }
+    Ok(format!("map1:{}", sha256_hex(&canon)))
+}
+
+/// Fingerprint a newline-delimited JSON (NDJSON) stream, yielding one MID
+/// result per document (JSON-STRICT + FULL, one call per record).
+///
+/// `r` can be a `&[u8]` slice or any `io::Read` (socket, pipe, file) of
+/// back-to-back JSON values — built on
+/// `serde_json::Deserializer::from_reader(...).into_iter()`, the same way
+/// NDJSON readers elsewhere work. Each record goes through the same
+/// `json_to_canon_value` + MID pipeline as `mid_full_json`, with the same
+/// `dup_found` → `ERR_DUP_KEY` precedence; `MAX_CANON_BYTES` applies per
+/// record, not to the stream as a whole. A parse failure on one record
+/// surfaces as that record's `Err` without aborting iteration of the rest.
+pub fn mid_stream<R: std::io::Read>(r: R) -> impl Iterator<Item = Result<String, MapError>> {
+    json_adapter::MidStream::new(r)
+}
+
 /// Validate pre-built CANON_BYTES and return MID (§3.7 fast-path).
 pub fn mid_from_canon_bytes(canon: &[u8]) -> Result<String, MapError> {
     mid::mid_from_canon_bytes(canon)
 }
 
+/// Validate pre-built CANON_BYTES and reconstruct the `MapValue` it
+/// encodes — the inverse of `canonical_bytes_full`/`canon_bytes_from_value`.
+///
+/// Enforces every limit `mid_from_canon_bytes` does (depth, size, key
+/// ordering/uniqueness); re-encoding the result reproduces `canon` exactly.
+pub fn value_from_canon_bytes(canon: &[u8]) -> Result<MapValue, MapError> {
+    decode::value_from_canon_bytes(canon)
+}
+
+// ── JSON normalization (opt-in, non-strict ingestion) ────────
+
+/// Compute a MID from raw UTF-8 JSON bytes, resolving duplicate-keyed
+/// objects per `policy` instead of always raising `ERR_DUP_KEY` the way
+/// `mid_full_json` does (unsorted keys are sorted, as in `mid_full_json`).
+///
+/// Object keys are sorted into canonical byte order and duplicates are
+/// resolved via `policy` (`DupPolicy::Reject` recovers `mid_full_json`'s
+/// strict behavior) before hashing, so this produces exactly the MID that
+/// `mid_full` would for the already-canonical descriptor — normalization
+/// only widens what's accepted on the way in, never what gets hashed.
+pub fn mid_full_json_normalized(raw: &[u8], policy: DupPolicy) -> Result<String, MapError> {
+    let val = json_adapter::parse_json_normalized(raw, policy)?;
+    mid_from_value(&val)
+}
+
+/// Return CANON_BYTES for raw JSON bytes via the normalized ingestion
+/// path. See `mid_full_json_normalized`.
+pub fn canonical_bytes_normalized(raw: &[u8], policy: DupPolicy) -> Result<Vec<u8>, MapError> {
+    let val = json_adapter::parse_json_normalized(raw, policy)?;
+    canon_bytes_from_value(&val)
+}
+
 // ── Internal helpers ─────────────────────────────────────────
 
 fn sha256_hex(data: &[u8]) -> String {
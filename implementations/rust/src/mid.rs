@@ -7,7 +7,7 @@ use sha2::{Digest, Sha256};
 
 use crate::constants::*;
 use crate::decode::mcf_decode_validate;
-use crate::encode::mcf_encode_value;
+use crate::encode::{mcf_encode_to_writer, mcf_encode_value};
 use crate::errors::*;
 use crate::value::MapValue;
 
@@ -21,17 +21,30 @@ fn sha256_hex(data: &[u8]) -> String {
 }
 
 /// Encode a canonical-model value to CANON_BYTES = CANON_HDR + MCF.
+///
+/// Fully encodes via [`mcf_encode_value`] before checking `MAX_CANON_BYTES`,
+/// rather than enforcing the limit mid-stream the way
+/// [`mid_from_value_streaming`] does: this is the entry point behind
+/// `mid_full`/`mid_bind`/`canonical_bytes_full`/`canonical_bytes_bind`/the
+/// JSON-STRICT paths, all of which must keep reporting whatever
+/// highest-precedence structural violation (§6.2) a complete traversal
+/// finds — `mcf_encode_to`'s own `?` already stops at the first such
+/// violation wherever it's encountered, so checking size only after a
+/// successful encode means a size-only failure can never preempt one.
+/// Failing fast on size instead (as a mid-stream `Write` limit must) can
+/// trip on an earlier sibling before traversal ever reaches a later,
+/// higher-precedence violation elsewhere in the tree.
 pub fn canon_bytes_from_value(val: &MapValue) -> Result<Vec<u8>, MapError> {
     let body = mcf_encode_value(val, 0)?;
-    let mut canon = Vec::with_capacity(CANON_HDR.len() + body.len());
-    canon.extend_from_slice(CANON_HDR);
-    canon.extend(body);
-    if canon.len() > MAX_CANON_BYTES {
+    if CANON_HDR.len() + body.len() > MAX_CANON_BYTES {
         return Err(MapError::new(
             ERR_LIMIT_SIZE,
             "canon bytes exceed MAX_CANON_BYTES",
         ));
     }
+    let mut canon = Vec::with_capacity(CANON_HDR.len() + body.len());
+    canon.extend_from_slice(CANON_HDR);
+    canon.extend_from_slice(&body);
     Ok(canon)
 }
 
@@ -41,6 +54,28 @@ pub fn mid_from_value(val: &MapValue) -> Result<String, MapError> {
     Ok(format!("map1:{}", sha256_hex(&canon)))
 }
 
+/// Compute a MID directly from a canonical-model value via a streaming
+/// encode, without ever materializing CANON_BYTES in memory.
+///
+/// Feeds `CANON_HDR` and then the MCF body — produced chunk-by-chunk by
+/// [`mcf_encode_to_writer`] — straight into a running `Sha256`, so no
+/// CANON_BYTES buffer is built: `mcf_encode_to` writes each LIST/MAP's
+/// entry count straight from the in-memory value, and its only scratch
+/// allocation is a per-MAP list of borrowed key slices for the order check.
+/// `MAX_CANON_BYTES` is still enforced, failing fast with `ERR_LIMIT_SIZE`
+/// the instant the running total would cross it rather than only after
+/// the whole value has been hashed.
+pub fn mid_from_value_streaming(val: &MapValue) -> Result<String, MapError> {
+    let mut hasher = Sha256::new();
+    hasher.update(CANON_HDR);
+    mcf_encode_to_writer(val, &mut hasher, 0)?;
+    let digest = hasher.finalize();
+    Ok(format!(
+        "map1:{}",
+        digest.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    ))
+}
+
 /// Validate pre-built CANON_BYTES and return MID.
 ///
 /// This is the "fast-path" entry point (§3.7) — it fully validates the
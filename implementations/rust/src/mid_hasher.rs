@@ -0,0 +1,295 @@
+//! SAX-style streaming encoder — computes a MID from push events without
+//! ever materializing a `MapValue` tree.
+//!
+//! `mid_full`, `mid_full_json`, and `canonical_bytes_full` all require the
+//! entire value already sitting in memory as a `MapValue`. `MidHasher`
+//! instead accepts a sequence of events (`begin_map`, `key`, `string`,
+//! `integer`, `big_int`, `boolean`, `bytes`, `begin_list`, `end`) and feeds
+//! canonical MCF bytes into a running SHA-256 state as they're produced,
+//! so a caller can compute a MID over input far larger than RAM.
+//!
+//! MCF's LIST/MAP tags are length-prefixed (the entry count comes before
+//! the entries), so `begin_map`/`begin_list` take that count up front —
+//! the one piece of lookahead the format demands of the caller. With the
+//! count in hand, the header can be written the instant the container
+//! opens and every child event streams straight into the digest as it's
+//! produced; nothing beyond the open-frame stack itself (a `remaining`
+//! counter per level, plus the previous key for MAPs) is ever retained,
+//! so memory use is bounded by nesting depth, not document size.
+//!
+//! A caller that can't determine a container's entry count before
+//! iterating it (e.g. driving this from a tokenizer over a format with no
+//! length prefix of its own, such as JSON) can't use `MidHasher` directly
+//! for that container — counting up front is a hard requirement of the
+//! wire format, not a convenience this type could paper over while still
+//! producing the right bytes in the right order. See `json_adapter`'s
+//! JSON bridge for how that case is actually handled.
+//!
+//! Key ordering (§3.5) is enforced incrementally per MAP level: `key()`
+//! compares each new key against the previous one at that level and
+//! rejects out-of-order or duplicate keys immediately, so only the
+//! current level's last key — not the whole tree — needs to be retained.
+
+use sha2::{Digest, Sha256};
+
+use crate::constants::*;
+use crate::encode::{validate_utf8_scalar, write_varint};
+use crate::errors::*;
+
+/// An open LIST or MAP container awaiting `end()`.
+enum Frame {
+    List { remaining: u32 },
+    Map { remaining: u32, prev_key: Option<Vec<u8>> },
+}
+
+/// Push-based MCF encoder that hashes as it goes.
+///
+/// Call `begin_map`/`begin_list`/`key`/`string`/`integer`/`boolean`/
+/// `bytes`/`end` to describe a value, then `finish` to get the MID. The
+/// call sequence must be well-formed (every `begin_*` matched by an `end`
+/// after exactly as many children as its declared count, every `key`
+/// immediately followed by exactly one value event) — this is a push API
+/// that trusts its caller, the same way other event-driven encoders do.
+pub struct MidHasher {
+    frames: Vec<Frame>,
+    hasher: Sha256,
+    root_written: bool,
+    total_len: usize,
+}
+
+impl Default for MidHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MidHasher {
+    pub fn new() -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(CANON_HDR);
+        Self {
+            frames: Vec::new(),
+            hasher,
+            root_written: false,
+            total_len: 0,
+        }
+    }
+
+    /// Open a MAP with exactly `count` entries. Must be matched by a
+    /// later `end()` after exactly `count` `key()`+value pairs.
+    pub fn begin_map(&mut self, count: u32) -> Result<(), MapError> {
+        if count > MAX_MAP_ENTRIES {
+            return Err(MapError::new(ERR_LIMIT_SIZE, "map entry count exceeds limit"));
+        }
+        self.open_container(TAG_MAP, count)?;
+        self.frames.push(Frame::Map { remaining: count, prev_key: None });
+        Ok(())
+    }
+
+    /// Open a LIST with exactly `count` entries. Must be matched by a
+    /// later `end()` after exactly `count` value events.
+    pub fn begin_list(&mut self, count: u32) -> Result<(), MapError> {
+        if count > MAX_LIST_ENTRIES {
+            return Err(MapError::new(ERR_LIMIT_SIZE, "list entry count exceeds limit"));
+        }
+        self.open_container(TAG_LIST, count)?;
+        self.frames.push(Frame::List { remaining: count });
+        Ok(())
+    }
+
+    /// Check depth and write the tag+count header for a container that's
+    /// about to be pushed. Writing happens here, before the frame exists,
+    /// so the header reaches the digest immediately instead of waiting
+    /// for `end()`.
+    fn open_container(&mut self, tag: u8, count: u32) -> Result<(), MapError> {
+        if self.frames.len() as u32 + 1 > MAX_DEPTH {
+            return Err(MapError::new(ERR_LIMIT_DEPTH, "depth exceeds MAX_DEPTH"));
+        }
+        let mut header = Vec::with_capacity(5);
+        header.push(tag);
+        header.extend_from_slice(&count.to_be_bytes());
+        self.write_raw(&header)
+    }
+
+    /// Emit a MAP key. Must be immediately followed by exactly one value
+    /// event (a scalar, or a `begin_list`/`begin_map` ... `end` pair).
+    pub fn key(&mut self, k: &str) -> Result<(), MapError> {
+        validate_utf8_scalar(k)?;
+        let raw = k.as_bytes().to_vec();
+        {
+            let frame = self
+                .frames
+                .last_mut()
+                .ok_or_else(|| MapError::new(ERR_SCHEMA, "key() outside a MAP"))?;
+            let Frame::Map { remaining, prev_key } = frame else {
+                return Err(MapError::new(ERR_SCHEMA, "key() while not inside a MAP"));
+            };
+            if *remaining == 0 {
+                return Err(MapError::new(
+                    ERR_SCHEMA,
+                    "key() called more times than the MAP's declared entry count",
+                ));
+            }
+            if let Some(prev) = prev_key {
+                match prev.as_slice().cmp(raw.as_slice()) {
+                    std::cmp::Ordering::Equal => {
+                        return Err(MapError::new(ERR_DUP_KEY, "duplicate key"));
+                    }
+                    std::cmp::Ordering::Greater => {
+                        return Err(MapError::new(ERR_KEY_ORDER, "key order violation"));
+                    }
+                    std::cmp::Ordering::Less => {}
+                }
+            }
+            *prev_key = Some(raw.clone());
+            *remaining -= 1;
+        }
+        let mut encoded = Vec::with_capacity(5 + raw.len());
+        encoded.push(TAG_STRING);
+        encoded.extend_from_slice(&(raw.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(&raw);
+        self.write_raw(&encoded)
+    }
+
+    /// Emit a STRING value.
+    pub fn string(&mut self, s: &str) -> Result<(), MapError> {
+        validate_utf8_scalar(s)?;
+        let raw = s.as_bytes();
+        let mut encoded = Vec::with_capacity(5 + raw.len());
+        encoded.push(TAG_STRING);
+        encoded.extend_from_slice(&(raw.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(raw);
+        self.emit_scalar(encoded)
+    }
+
+    /// Emit a BYTES value.
+    pub fn bytes(&mut self, b: &[u8]) -> Result<(), MapError> {
+        let mut encoded = Vec::with_capacity(5 + b.len());
+        encoded.push(TAG_BYTES);
+        encoded.extend_from_slice(&(b.len() as u32).to_be_bytes());
+        encoded.extend_from_slice(b);
+        self.emit_scalar(encoded)
+    }
+
+    /// Emit an INTEGER value.
+    pub fn integer(&mut self, i: i64) -> Result<(), MapError> {
+        let mut encoded = Vec::with_capacity(9);
+        encoded.push(TAG_INTEGER);
+        encoded.extend_from_slice(&i.to_be_bytes());
+        self.emit_scalar(encoded)
+    }
+
+    /// Emit a BOOLEAN value.
+    pub fn boolean(&mut self, b: bool) -> Result<(), MapError> {
+        self.emit_scalar(vec![TAG_BOOLEAN, if b { 0x01 } else { 0x00 }])
+    }
+
+    /// Emit a BIGINT value: `negative` plus a minimal big-endian
+    /// `magnitude`, the same representation `MapValue::BigInt` carries.
+    /// Non-minimal encodings are rejected here too, for the same reason
+    /// `mcf_encode_to`'s BigInt arm rejects them: a negative zero or a
+    /// leading zero magnitude byte would make the wire form non-bijective.
+    pub fn big_int(&mut self, negative: bool, magnitude: &[u8]) -> Result<(), MapError> {
+        if negative && magnitude.is_empty() {
+            return Err(MapError::new(ERR_CANON_MCF, "negative-zero BigInt"));
+        }
+        if magnitude.first() == Some(&0) {
+            return Err(MapError::new(
+                ERR_CANON_MCF,
+                "BigInt magnitude has a leading zero byte",
+            ));
+        }
+        let mut encoded = Vec::with_capacity(2 + magnitude.len() + 2);
+        encoded.push(TAG_BIGINT);
+        encoded.push(if negative { 0x01 } else { 0x00 });
+        write_varint(&mut encoded, magnitude.len() as u64)?;
+        encoded.extend_from_slice(magnitude);
+        self.emit_scalar(encoded)
+    }
+
+    /// Close the most recently opened LIST/MAP. Its header and every
+    /// child were already streamed into the digest as they were produced,
+    /// so this just checks the declared count was fully satisfied and
+    /// accounts for the now-closed container as a value of whatever
+    /// encloses it.
+    pub fn end(&mut self) -> Result<(), MapError> {
+        let frame = self
+            .frames
+            .pop()
+            .ok_or_else(|| MapError::new(ERR_CANON_MCF, "end() with no open container"))?;
+        let remaining = match frame {
+            Frame::List { remaining } => remaining,
+            Frame::Map { remaining, .. } => remaining,
+        };
+        if remaining != 0 {
+            return Err(MapError::new(
+                ERR_SCHEMA,
+                "end() before the container's declared entry count was reached",
+            ));
+        }
+        self.account_for_value()
+    }
+
+    /// Finish encoding and return the MID. Errors if a container is
+    /// still open or no value was ever emitted.
+    pub fn finish(self) -> Result<String, MapError> {
+        if !self.frames.is_empty() {
+            return Err(MapError::new(ERR_CANON_MCF, "finish() with an unclosed container"));
+        }
+        if !self.root_written {
+            return Err(MapError::new(ERR_CANON_MCF, "finish() with no value written"));
+        }
+        let digest = self.hasher.finalize();
+        Ok(format!(
+            "map1:{}",
+            digest.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+        ))
+    }
+
+    /// Track the running CANON_BYTES length and fail fast if it would
+    /// exceed `MAX_CANON_BYTES`, the same limit `canon_bytes_from_value`
+    /// enforces on a fully materialized buffer, then feed `bytes` straight
+    /// into the digest — nothing is ever buffered.
+    fn write_raw(&mut self, bytes: &[u8]) -> Result<(), MapError> {
+        self.total_len += bytes.len();
+        if CANON_HDR.len() + self.total_len > MAX_CANON_BYTES {
+            return Err(MapError::new(ERR_LIMIT_SIZE, "canon bytes exceed MAX_CANON_BYTES"));
+        }
+        self.hasher.update(bytes);
+        Ok(())
+    }
+
+    /// Write a complete scalar's encoded bytes, then account for it.
+    fn emit_scalar(&mut self, encoded: Vec<u8>) -> Result<(), MapError> {
+        self.write_raw(&encoded)?;
+        self.account_for_value()
+    }
+
+    /// Record that one value (a scalar just written, or a LIST/MAP just
+    /// closed by `end()`) is complete: decrement the enclosing LIST's
+    /// remaining count, leave the enclosing MAP's count alone (`key()`
+    /// already accounted for it), or — if nothing encloses it — mark the
+    /// MCF root as written.
+    fn account_for_value(&mut self) -> Result<(), MapError> {
+        match self.frames.last_mut() {
+            Some(Frame::List { remaining }) => {
+                if *remaining == 0 {
+                    return Err(MapError::new(
+                        ERR_SCHEMA,
+                        "value emitted beyond the LIST's declared entry count",
+                    ));
+                }
+                *remaining -= 1;
+                Ok(())
+            }
+            Some(Frame::Map { .. }) => Ok(()),
+            None => {
+                if self.root_written {
+                    return Err(MapError::new(ERR_CANON_MCF, "trailing value after MCF root"));
+                }
+                self.root_written = true;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Zero-copy MCF validation over a memory-mapped region.
+//!
+//! `mid_from_canon_bytes` already avoids allocating per-key strings (see
+//! `decode`'s `prev_key_range`); this module goes one step further and
+//! lets the *input* itself live outside the heap. `CanonMmap` wraps a
+//! `memmap2::Mmap` so a pre-serialized CANON_BYTES file can be validated
+//! and hashed straight from the OS page cache, instead of being copied
+//! wholesale into a `Vec<u8>` first. The size limit is unchanged: a file
+//! larger than `MAX_CANON_BYTES` is rejected with `ERR_LIMIT_SIZE` before
+//! any validation, exactly as `mid_from_canon_bytes` rejects such a slice.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::errors::*;
+use crate::mid::mid_from_canon_bytes;
+
+/// A memory-mapped CANON_BYTES region. Validation and hashing both read
+/// straight out of the mapping; nothing is copied to the heap up front.
+pub struct CanonMmap {
+    mmap: Mmap,
+}
+
+impl CanonMmap {
+    /// Map `path` read-only.
+    ///
+    /// Inherits `memmap2::Mmap::map`'s safety contract: the mapping is
+    /// only sound if nothing else truncates or mutates the underlying
+    /// file for the lifetime of the returned `CanonMmap`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MapError> {
+        let file = File::open(path)
+            .map_err(|e| MapError::new(ERR_IO, format!("open mmap file failed: {}", e)))?;
+        // SAFETY: caller is responsible for not mutating/truncating the
+        // file for the lifetime of the returned `CanonMmap`.
+        let mmap = unsafe { Mmap::map(&file) }
+            .map_err(|e| MapError::new(ERR_IO, format!("mmap failed: {}", e)))?;
+        Ok(Self { mmap })
+    }
+
+    /// Borrow the mapped region as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+/// Validate and hash a memory-mapped CANON_BYTES region without copying it
+/// into the heap.
+pub fn mid_from_canon_mmap(canon: &CanonMmap) -> Result<String, MapError> {
+    mid_from_canon_bytes(canon.as_bytes())
+}
@@ -0,0 +1,170 @@
+//! Self-describing MID identifiers (non-normative extension).
+//!
+//! `mid_from_canon_bytes` hardwires `"map1:" || hex_lower(sha256(...))`
+//! (§5.3) — fine today, but a caller stuck with that spelling can't move
+//! off SHA-256 later without either breaking every existing MID or
+//! inventing their own way to tag which hash produced a new one. This
+//! module adds an alternate identifier mode, modeled on multihash/CID,
+//! that embeds the hash algorithm and digest length in the identifier
+//! itself so a verifier can tell which hash to recompute.
+//!
+//! Wire format: `"map1:" || multibase_prefix || multibase(varint(hash_code)
+//! || varint(digest_len) || digest)`. `hash_code` is the algorithm's
+//! standard multicodec value (https://github.com/multiformats/multicodec),
+//! `digest_len` is the digest byte count, and both are unsigned LEB128
+//! varints (https://github.com/multiformats/unsigned-varint). The payload
+//! is multibase-tagged with `u` (base64url, no padding) rather than the
+//! more common base58btc, since `base64` is already a dependency here and
+//! this crate has no base58 support to add for one feature.
+//!
+//! The existing hex-SHA-256 spelling (`"map1:" + 64 hex chars`) remains
+//! the default and is untouched by this module — `mid_multihash` is an
+//! opt-in alternate output, not a replacement.
+
+use base64::Engine;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::errors::*;
+
+/// Multicodec table values for the digest algorithms this module supports.
+const CODE_SHA2_256: u64 = 0x12;
+const CODE_SHA2_512: u64 = 0x13;
+const CODE_BLAKE3: u64 = 0x1e;
+
+/// Multibase prefix for base64url, no padding.
+const MULTIBASE_BASE64URL_NOPAD: char = 'u';
+
+/// Digest algorithm for `mid_multihash` / `parse_mid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MidAlgo {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl MidAlgo {
+    fn multicodec(self) -> u64 {
+        match self {
+            MidAlgo::Sha256 => CODE_SHA2_256,
+            MidAlgo::Sha512 => CODE_SHA2_512,
+            MidAlgo::Blake3 => CODE_BLAKE3,
+        }
+    }
+
+    fn from_multicodec(code: u64) -> Option<Self> {
+        match code {
+            CODE_SHA2_256 => Some(MidAlgo::Sha256),
+            CODE_SHA2_512 => Some(MidAlgo::Sha512),
+            CODE_BLAKE3 => Some(MidAlgo::Blake3),
+            _ => None,
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            MidAlgo::Sha256 => Sha256::digest(data).to_vec(),
+            MidAlgo::Sha512 => Sha512::digest(data).to_vec(),
+            MidAlgo::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Append `n` to `out` as an unsigned LEB128 varint.
+fn write_varint(out: &mut Vec<u8>, mut n: u64) {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Read an unsigned LEB128 varint from the front of `buf`, returning the
+/// value and the number of bytes consumed.
+fn read_varint(buf: &[u8]) -> Result<(u64, usize), MapError> {
+    let mut value: u64 = 0;
+    let mut shift = 0u32;
+    for (i, &byte) in buf.iter().enumerate() {
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, i + 1));
+        }
+        shift += 7;
+        if shift >= 64 {
+            return Err(MapError::new(ERR_SCHEMA, "multihash varint too long"));
+        }
+    }
+    Err(MapError::new(ERR_SCHEMA, "multihash varint truncated"))
+}
+
+/// Compute a self-describing MID over `canon` (CANON_BYTES) using `algo`.
+///
+/// Produces `"map1:"` followed by a multibase-tagged (`u` = base64url, no
+/// padding) encoding of `varint(hash_code) || varint(digest_len) ||
+/// digest`. Pair with `parse_mid` to recover `algo` and the raw digest.
+pub fn mid_multihash(canon: &[u8], algo: MidAlgo) -> String {
+    let digest = algo.digest(canon);
+    let mut payload = Vec::with_capacity(2 + digest.len());
+    write_varint(&mut payload, algo.multicodec());
+    write_varint(&mut payload, digest.len() as u64);
+    payload.extend_from_slice(&digest);
+
+    let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&payload);
+    format!("map1:{}{}", MULTIBASE_BASE64URL_NOPAD, encoded)
+}
+
+/// The algorithm and raw digest recovered from a `mid_multihash` string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedMid {
+    pub algo: MidAlgo,
+    pub digest: Vec<u8>,
+}
+
+/// Parse a `mid_multihash`-produced identifier back into its algorithm and
+/// raw digest, so a verifier knows which hash to recompute over its own
+/// CANON_BYTES before comparing.
+///
+/// Returns `ERR_SCHEMA` for anything that isn't shaped like a
+/// `mid_multihash` output — in particular the default hex-SHA-256
+/// spelling (`"map1:" || 64 hex chars`, no multibase prefix) is a
+/// different, non-multihash form and is rejected here rather than
+/// silently reinterpreted.
+pub fn parse_mid(mid: &str) -> Result<ParsedMid, MapError> {
+    let rest = mid
+        .strip_prefix("map1:")
+        .ok_or_else(|| MapError::new(ERR_SCHEMA, "MID missing map1: prefix"))?;
+
+    let mut chars = rest.chars();
+    let tag = chars
+        .next()
+        .ok_or_else(|| MapError::new(ERR_SCHEMA, "MID body is empty"))?;
+    if tag != MULTIBASE_BASE64URL_NOPAD {
+        return Err(MapError::new(
+            ERR_SCHEMA,
+            format!("not a multihash MID (multibase prefix '{}')", tag),
+        ));
+    }
+    let encoded = chars.as_str();
+
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| MapError::new(ERR_SCHEMA, format!("invalid multibase payload: {}", e)))?;
+
+    let (code, code_len) = read_varint(&payload)?;
+    let algo = MidAlgo::from_multicodec(code)
+        .ok_or_else(|| MapError::new(ERR_SCHEMA, format!("unknown hash multicodec 0x{:x}", code)))?;
+    let (digest_len, len_len) = read_varint(&payload[code_len..])?;
+    let digest_start = code_len + len_len;
+    let digest_end = digest_start + digest_len as usize;
+    if digest_end != payload.len() {
+        return Err(MapError::new(ERR_SCHEMA, "multihash digest length mismatch"));
+    }
+
+    Ok(ParsedMid {
+        algo,
+        digest: payload[digest_start..digest_end].to_vec(),
+    })
+}
@@ -18,7 +18,7 @@ use crate::value::MapValue;
 // if you decode "~1" before "~0", the string "~01" decodes wrong.
 // We handle this character-by-character to avoid that trap.
 
-fn parse_pointer(ptr: &str) -> Result<Vec<String>, MapError> {
+pub(crate) fn parse_pointer(ptr: &str) -> Result<Vec<String>, MapError> {
     if ptr.is_empty() {
         return Ok(Vec::new()); // whole-document pointer (rule 2.3.e)
     }
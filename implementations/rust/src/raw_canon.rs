@@ -0,0 +1,146 @@
+//! `RawCanon` — a borrowed, lazily-traversed handle over CANON_BYTES.
+//!
+//! `mid_from_canon_bytes` validates and hashes a whole blob in one pass,
+//! discarding structure as it goes. A caller holding one big validated
+//! CANON_BYTES document and wanting many `mid_bind`-style answers out of
+//! it — "what's the MID of just `/a/b`?" — would otherwise pay a full
+//! top-to-bottom re-validation per query. `RawCanon::open` walks the
+//! top-level MAP once (the same structural walk `mcf_decode_validate`
+//! already does) and records each entry's key and byte range; `get`
+//! then only has to resolve the path's remaining levels and
+//! validate+hash the one sub-slice it lands on.
+
+use crate::constants::*;
+use crate::decode::{mcf_decode_validate, read_u32be};
+use crate::errors::*;
+use crate::mid::mid_from_canon_bytes;
+use crate::projection::parse_pointer;
+
+/// A top-level MAP entry: its key, plus the byte range of its *value*
+/// (tag included) within the original CANON_BYTES buffer.
+#[derive(Clone, Copy)]
+struct Entry<'a> {
+    key: &'a str,
+    range: (usize, usize),
+}
+
+/// A borrowed, indexed view over a validated CANON_BYTES blob.
+///
+/// Holds a `&'a [u8]` — `RawCanon` never copies the document, so it
+/// can't outlive the buffer it was opened from.
+pub struct RawCanon<'a> {
+    buf: &'a [u8],
+    entries: Vec<Entry<'a>>,
+}
+
+impl<'a> RawCanon<'a> {
+    /// Validate `canon` (CANON_HDR + MCF) exactly like `mid_from_canon_bytes`
+    /// would, then index the root MAP's entries for later point queries.
+    ///
+    /// If the root isn't a MAP, validation still succeeds (a scalar or
+    /// LIST root is perfectly valid CANON_BYTES) but there are no entries
+    /// to index — `get` on any non-empty pointer against such a document
+    /// returns `ERR_SCHEMA`, same as `bind_project` does for a non-MAP root.
+    pub fn open(canon: &'a [u8]) -> Result<Self, MapError> {
+        if canon.len() > MAX_CANON_BYTES {
+            return Err(MapError::new(
+                ERR_LIMIT_SIZE,
+                "canon bytes exceed MAX_CANON_BYTES",
+            ));
+        }
+        if !canon.starts_with(CANON_HDR) {
+            return Err(MapError::new(ERR_CANON_HDR, "bad CANON_HDR"));
+        }
+
+        let root_off = CANON_HDR.len();
+        let end = mcf_decode_validate(canon, root_off, 0)?;
+        if end != canon.len() {
+            return Err(MapError::new(
+                ERR_CANON_MCF,
+                "trailing bytes after MCF root",
+            ));
+        }
+
+        let entries = if canon[root_off] == TAG_MAP {
+            index_map_entries(canon, root_off)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self { buf: canon, entries })
+    }
+
+    /// Iterate the root MAP's keys without allocating or decoding any
+    /// values. Empty if the root isn't a MAP.
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.entries.iter().map(|e| e.key)
+    }
+
+    /// Compute the MID of the sub-value at `pointer` (RFC 6901, same
+    /// syntax as `mid_bind`'s pointers), resolving one level of the path
+    /// at a time (indexing each intermediate MAP re-validates its entries)
+    /// and hashing only the final sub-slice.
+    ///
+    /// The empty pointer `""` returns the MID of the whole document. A
+    /// pointer through a LIST, through a non-MAP scalar, or naming a
+    /// missing key is `ERR_SCHEMA`.
+    pub fn get(&self, pointer: &str) -> Result<String, MapError> {
+        let tokens = parse_pointer(pointer)?;
+        if tokens.is_empty() {
+            return mid_from_canon_bytes(self.buf);
+        }
+
+        let mut level = self.entries.clone();
+        let mut range = None;
+        for (i, tok) in tokens.iter().enumerate() {
+            let entry = level
+                .iter()
+                .find(|e| e.key == tok.as_str())
+                .copied()
+                .ok_or_else(|| {
+                    MapError::new(ERR_SCHEMA, format!("pointer {} not found in document", pointer))
+                })?;
+            range = Some(entry.range);
+            if i + 1 < tokens.len() {
+                level = index_map_entries(self.buf, entry.range.0)?;
+            }
+        }
+
+        let (start, end) = range.expect("at least one pointer token was resolved");
+        let mut sub_canon = Vec::with_capacity(CANON_HDR.len() + (end - start));
+        sub_canon.extend_from_slice(CANON_HDR);
+        sub_canon.extend_from_slice(&self.buf[start..end]);
+        mid_from_canon_bytes(&sub_canon)
+    }
+}
+
+/// Index the entries of the MAP value starting at `map_start` (the
+/// `TAG_MAP` byte itself), returning each key and its value's byte range.
+///
+/// Mirrors `mcf_decode_validate`'s own MAP-walking loop byte-for-byte,
+/// except it records ranges instead of checking ordering/uniqueness —
+/// those were already enforced by the full-document walk in `open`.
+fn index_map_entries<'a>(buf: &'a [u8], map_start: usize) -> Result<Vec<Entry<'a>>, MapError> {
+    if buf[map_start] != TAG_MAP {
+        return Err(MapError::new(ERR_SCHEMA, "pointer traverses a non-MAP value"));
+    }
+    let (count, mut off) = read_u32be(buf, map_start + 1)?;
+
+    let mut entries = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (key_len, key_off) = read_u32be(buf, off + 1)?;
+        let key_start = key_off;
+        let key_end = key_off + key_len as usize;
+        let key = std::str::from_utf8(&buf[key_start..key_end])
+            .map_err(|e| MapError::new(ERR_UTF8, format!("invalid UTF-8 in map key: {}", e)))?;
+
+        let value_start = key_end;
+        let value_end = mcf_decode_validate(buf, value_start, 0)?;
+        entries.push(Entry {
+            key,
+            range: (value_start, value_end),
+        });
+        off = value_end;
+    }
+    Ok(entries)
+}
@@ -0,0 +1,230 @@
+//! Typed schema subsystem (v1.1 extension) — per-path type declarations
+//! validated before hashing.
+//!
+//! `MapValue` already distinguishes BOOLEAN from STRING and INTEGER from
+//! STRING (v1.1's whole point), but nothing stops a producer from
+//! accidentally emitting `String("true".into())` where a consumer expects
+//! `Boolean`. `MapSchema` gives that consumer a way to declare, ahead of
+//! time, which type belongs at which path — using the same RFC 6901
+//! pointer syntax `mid_bind` already speaks — and to reject a mismatching
+//! descriptor with a dedicated `ERR_SCHEMA_MISMATCH` before it is ever
+//! hashed into a MID.
+//!
+//! Not one of the spec's 9 normative error codes (§6): this is a v1.1
+//! extension surface, so it gets its own code outside `PRECEDENCE`,
+//! exactly like `ERR_IO`.
+
+use crate::errors::*;
+use crate::projection::parse_pointer;
+use crate::value::MapValue;
+
+/// One of the six canonical types (§3.1), plus the v1.1 BigInt extension,
+/// a schema path can declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    String,
+    Bytes,
+    List,
+    Map,
+    Boolean,
+    Integer,
+    BigInt,
+}
+
+impl SchemaType {
+    fn matches(self, val: &MapValue) -> bool {
+        matches!(
+            (self, val),
+            (SchemaType::String, MapValue::String(_))
+                | (SchemaType::Bytes, MapValue::Bytes(_))
+                | (SchemaType::List, MapValue::List(_))
+                | (SchemaType::Map, MapValue::Map(_))
+                | (SchemaType::Boolean, MapValue::Boolean(_))
+                | (SchemaType::Integer, MapValue::Integer(_))
+                | (SchemaType::BigInt, MapValue::BigInt(..))
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            SchemaType::String => "STRING",
+            SchemaType::Bytes => "BYTES",
+            SchemaType::List => "LIST",
+            SchemaType::Map => "MAP",
+            SchemaType::Boolean => "BOOLEAN",
+            SchemaType::Integer => "INTEGER",
+            SchemaType::BigInt => "BIGINT",
+        }
+    }
+}
+
+/// A single declared path: tokens are RFC 6901 pointer segments, where a
+/// literal `"*"` segment is a wildcard matching any LIST index or MAP key.
+#[derive(Debug, Clone)]
+struct FieldDecl {
+    path: Vec<String>,
+    ty: SchemaType,
+    required: bool,
+}
+
+/// A set of per-path type declarations, checked against a descriptor
+/// before it is hashed.
+///
+/// Paths use the same RFC 6901 pointer syntax as `mid_bind`, plus a
+/// literal `"*"` segment meaning "every element of the LIST/MAP here".
+/// `required` constrains presence of each non-wildcard segment of the
+/// path (for `/items/*/name`, `/items` and a `name` key in every element
+/// of it); a `"*"` segment itself requires nothing, so a path like
+/// `/flags/*` only ever constrains the type of whatever happens to match.
+#[derive(Debug, Clone, Default)]
+pub struct MapSchema {
+    fields: Vec<FieldDecl>,
+    forbid_additional: bool,
+}
+
+impl MapSchema {
+    /// An empty schema: every MAP descriptor passes unless `forbid_additional_keys`
+    /// was also set, in which case only an empty MAP passes.
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            forbid_additional: false,
+        }
+    }
+
+    /// Declare that `pointer` must be present and of type `ty`.
+    pub fn require(self, pointer: &str, ty: SchemaType) -> Result<Self, MapError> {
+        self.declare(pointer, ty, true)
+    }
+
+    /// Declare that, if present, `pointer` must be of type `ty`.
+    pub fn optional(self, pointer: &str, ty: SchemaType) -> Result<Self, MapError> {
+        self.declare(pointer, ty, false)
+    }
+
+    fn declare(mut self, pointer: &str, ty: SchemaType, required: bool) -> Result<Self, MapError> {
+        let path = parse_pointer(pointer)?;
+        if path.is_empty() {
+            return Err(MapError::new(
+                ERR_SCHEMA,
+                "schema path must not be the empty pointer",
+            ));
+        }
+        self.fields.push(FieldDecl { path, ty, required });
+        Ok(self)
+    }
+
+    /// Reject any MAP key, at any declared level, that no path in this
+    /// schema covers. Off by default (schemas are opt-in allow-lists for
+    /// the paths they name, not closed by default).
+    pub fn forbid_additional_keys(mut self) -> Self {
+        self.forbid_additional = true;
+        self
+    }
+
+    /// Validate `descriptor` against this schema.
+    ///
+    /// Root must be a MAP, matching `mid_bind`'s own root requirement.
+    /// Any path whose runtime type diverges from its declaration raises
+    /// `ERR_SCHEMA_MISMATCH`, as does a missing required path or (with
+    /// `forbid_additional_keys`) an undeclared key.
+    pub fn validate(&self, descriptor: &MapValue) -> Result<(), MapError> {
+        if !matches!(descriptor, MapValue::Map(_)) {
+            return Err(MapError::new(ERR_SCHEMA, "schema root must be a MAP"));
+        }
+        let active: Vec<&FieldDecl> = self.fields.iter().collect();
+        validate_node(descriptor, &active, 0, self.forbid_additional, "")
+    }
+}
+
+fn validate_node(
+    val: &MapValue,
+    decls: &[&FieldDecl],
+    depth: usize,
+    forbid_additional: bool,
+    path_str: &str,
+) -> Result<(), MapError> {
+    // Declarations fully consumed by now: their type applies to `val` itself.
+    for d in decls.iter().filter(|d| d.path.len() == depth) {
+        if !d.ty.matches(val) {
+            return Err(MapError::new(
+                ERR_SCHEMA_MISMATCH,
+                format!(
+                    "{}: expected {}, found {}",
+                    display_path(path_str),
+                    d.ty.name(),
+                    val
+                ),
+            ));
+        }
+    }
+
+    match val {
+        MapValue::Map(entries) => {
+            for (key, child) in entries {
+                let matched: Vec<&FieldDecl> = decls
+                    .iter()
+                    .filter(|d| d.path.len() > depth && (d.path[depth] == *key || d.path[depth] == "*"))
+                    .copied()
+                    .collect();
+                if forbid_additional && matched.is_empty() {
+                    return Err(MapError::new(
+                        ERR_SCHEMA_MISMATCH,
+                        format!("{}/{}: key not declared by schema", display_path(path_str), key),
+                    ));
+                }
+                let child_path = format!("{}/{}", path_str, key);
+                validate_node(child, &matched, depth + 1, forbid_additional, &child_path)?;
+            }
+            for d in decls
+                .iter()
+                .filter(|d| d.required && d.path.len() > depth && d.path[depth] != "*")
+            {
+                let key = &d.path[depth];
+                if !entries.iter().any(|(k, _)| k == key) {
+                    return Err(MapError::new(
+                        ERR_SCHEMA_MISMATCH,
+                        format!("{}/{}: required key missing", display_path(path_str), key),
+                    ));
+                }
+            }
+        }
+        MapValue::List(items) => {
+            for (i, item) in items.iter().enumerate() {
+                let matched: Vec<&FieldDecl> = decls
+                    .iter()
+                    .filter(|d| d.path.len() > depth && d.path[depth] == "*")
+                    .copied()
+                    .collect();
+                let child_path = format!("{}/{}", path_str, i);
+                validate_node(item, &matched, depth + 1, forbid_additional, &child_path)?;
+            }
+        }
+        MapValue::String(_)
+        | MapValue::Bytes(_)
+        | MapValue::Boolean(_)
+        | MapValue::Integer(_)
+        | MapValue::BigInt(..) => {
+            if decls.iter().any(|d| d.path.len() > depth) {
+                return Err(MapError::new(
+                    ERR_SCHEMA_MISMATCH,
+                    format!(
+                        "{}: schema path continues past non-container value {}",
+                        display_path(path_str),
+                        val
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn display_path(path_str: &str) -> &str {
+    if path_str.is_empty() {
+        "/"
+    } else {
+        path_str
+    }
+}
@@ -0,0 +1,561 @@
+//! serde bridge — lets arbitrary `T: Serialize` be MID'd via `mid_of(&t)`,
+//! without hand-building a `MapValue` tree, mirroring how the serde
+//! ecosystem layers typed models over a generic `Value` tree.
+//!
+//! `ValueSerializer` is a `serde::Serializer` whose output *is* a
+//! `MapValue`: structs/maps become `Map`, seqs become `List`, integers
+//! become `Integer`, bools become `Boolean`, byte arrays become `Bytes`.
+//! The result is run through [`canonicalize_map`] so field order doesn't
+//! matter to the caller. `f32`/`f64`, `None`/unit, and non-string map
+//! keys are rejected with `ERR_TYPE`/`ERR_SCHEMA` exactly as the
+//! JSON-STRICT path rejects them, so `mid_of(&t)` and `mid_full_json`
+//! agree on equivalent data. `ValueDeserializer` reads a `MapValue` back
+//! into any `T: Deserialize`, so `canonical_bytes_full` → typed struct
+//! round-trips.
+
+use serde::de::value::StrDeserializer;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::{
+    self, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::canonicalize::{canonicalize_map, DupPolicy};
+use crate::errors::*;
+use crate::mid::mid_from_value;
+use crate::value::{big_int_to_i128, MapValue};
+
+impl ser::Error for MapError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        MapError::new(ERR_TYPE, msg.to_string())
+    }
+}
+
+impl de::Error for MapError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        MapError::new(ERR_TYPE, msg.to_string())
+    }
+}
+
+// ── Serialize: T -> MapValue ─────────────────────────────────
+
+/// Convert any `T: Serialize` into the canonical model.
+pub fn to_map_value<T: Serialize>(value: &T) -> Result<MapValue, MapError> {
+    value.serialize(ValueSerializer)
+}
+
+/// Compute a MID directly from any `T: Serialize`.
+pub fn mid_of<T: Serialize>(value: &T) -> Result<String, MapError> {
+    mid_from_value(&to_map_value(value)?)
+}
+
+/// Wrap a freshly-built MAP with a variant name, matching the externally
+/// tagged representation serde_json uses by default (`{"Variant": ...}`).
+fn tag_variant(variant: &'static str, payload: MapValue) -> Result<MapValue, MapError> {
+    canonicalize_map(vec![(variant.to_owned(), payload)], DupPolicy::Reject)
+}
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<MapValue, MapError> {
+        Ok(MapValue::Boolean(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i16(self, v: i16) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i32(self, v: i32) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_i64(self, v: i64) -> Result<MapValue, MapError> {
+        Ok(MapValue::Integer(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u16(self, v: u16) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u32(self, v: u32) -> Result<MapValue, MapError> {
+        self.serialize_i64(v as i64)
+    }
+    fn serialize_u64(self, v: u64) -> Result<MapValue, MapError> {
+        // Values above i64::MAX promote to BigInt rather than erroring, so
+        // this agrees with the JSON front-end's overflow handling (see
+        // `json_to_canon_value` in `json_adapter`).
+        match i64::try_from(v) {
+            Ok(v) => Ok(MapValue::Integer(v)),
+            Err(_) => Ok(MapValue::big_int_from_u64(v)),
+        }
+    }
+    fn serialize_f32(self, _v: f32) -> Result<MapValue, MapError> {
+        Err(MapError::new(ERR_TYPE, "float not allowed"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<MapValue, MapError> {
+        Err(MapError::new(ERR_TYPE, "float not allowed"))
+    }
+    fn serialize_char(self, v: char) -> Result<MapValue, MapError> {
+        Ok(MapValue::String(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<MapValue, MapError> {
+        Ok(MapValue::String(v.to_owned()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<MapValue, MapError> {
+        Ok(MapValue::Bytes(v.to_vec()))
+    }
+    fn serialize_none(self) -> Result<MapValue, MapError> {
+        Err(MapError::new(ERR_TYPE, "None not allowed"))
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<MapValue, MapError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<MapValue, MapError> {
+        Err(MapError::new(ERR_TYPE, "unit not allowed"))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<MapValue, MapError> {
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<MapValue, MapError> {
+        Ok(MapValue::String(variant.to_owned()))
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<MapValue, MapError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<MapValue, MapError> {
+        tag_variant(variant, value.serialize(ValueSerializer)?)
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, MapError> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, MapError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, MapError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, MapError> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, MapError> {
+        Ok(MapSerializer {
+            entries: Vec::new(),
+            next_key: None,
+            variant: None,
+        })
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, MapError> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len),
+            next_key: None,
+            variant: None,
+        })
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, MapError> {
+        Ok(MapSerializer {
+            entries: Vec::with_capacity(len),
+            next_key: None,
+            variant: Some(variant),
+        })
+    }
+}
+
+/// Rejects anything but a string-like map key, matching JSON-STRICT's
+/// "object keys are always strings" policy.
+struct MapKeySerializer;
+
+macro_rules! key_not_string {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, _v: $ty) -> Result<String, MapError> {
+                Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+            }
+        )*
+    };
+}
+
+impl Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = MapError;
+    type SerializeSeq = ser::Impossible<String, MapError>;
+    type SerializeTuple = ser::Impossible<String, MapError>;
+    type SerializeTupleStruct = ser::Impossible<String, MapError>;
+    type SerializeTupleVariant = ser::Impossible<String, MapError>;
+    type SerializeMap = ser::Impossible<String, MapError>;
+    type SerializeStruct = ser::Impossible<String, MapError>;
+    type SerializeStructVariant = ser::Impossible<String, MapError>;
+
+    fn serialize_str(self, v: &str) -> Result<String, MapError> {
+        Ok(v.to_owned())
+    }
+    fn serialize_char(self, v: char) -> Result<String, MapError> {
+        Ok(v.to_string())
+    }
+
+    key_not_string!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+    );
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_none(self) -> Result<String, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<String, MapError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<String, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, MapError> {
+        Ok(variant.to_owned())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, MapError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, MapError> {
+        Err(MapError::new(ERR_SCHEMA, "non-string map key"))
+    }
+}
+
+struct SeqSerializer {
+    items: Vec<MapValue>,
+    variant: Option<&'static str>,
+}
+
+impl SeqSerializer {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn finish(self) -> Result<MapValue, MapError> {
+        let list = MapValue::List(self.items);
+        match self.variant {
+            Some(variant) => tag_variant(variant, list),
+            None => Ok(list),
+        }
+    }
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+impl SerializeTuple for SeqSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+impl SerializeTupleVariant for SeqSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+
+struct MapSerializer {
+    entries: Vec<(String, MapValue)>,
+    next_key: Option<String>,
+    variant: Option<&'static str>,
+}
+
+impl MapSerializer {
+    fn push_field(&mut self, key: String, value: MapValue) {
+        self.entries.push((key, value));
+    }
+    fn finish(self) -> Result<MapValue, MapError> {
+        let map = canonicalize_map(self.entries, DupPolicy::Reject)?;
+        match self.variant {
+            Some(variant) => tag_variant(variant, map),
+            None => Ok(map),
+        }
+    }
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), MapError> {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), MapError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| MapError::new(ERR_SCHEMA, "serialize_value before serialize_key"))?;
+        self.push_field(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+impl SerializeStruct for MapSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), MapError> {
+        self.push_field(key.to_owned(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+impl SerializeStructVariant for MapSerializer {
+    type Ok = MapValue;
+    type Error = MapError;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), MapError> {
+        self.push_field(key.to_owned(), value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<MapValue, MapError> {
+        self.finish()
+    }
+}
+
+// ── Deserialize: MapValue -> T ───────────────────────────────
+
+/// Read a `T: Deserialize` back out of the canonical model.
+pub fn from_map_value<'de, T: Deserialize<'de>>(value: &'de MapValue) -> Result<T, MapError> {
+    T::deserialize(ValueDeserializer { value })
+}
+
+struct ValueDeserializer<'de> {
+    value: &'de MapValue,
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = MapError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, MapError> {
+        match self.value {
+            MapValue::Boolean(b) => visitor.visit_bool(*b),
+            MapValue::Integer(i) => visitor.visit_i64(*i),
+            MapValue::BigInt(negative, magnitude) => {
+                match big_int_to_i128(*negative, magnitude) {
+                    Some(v) => visitor.visit_i128(v),
+                    // Doesn't fit i128 either — hand back the decimal
+                    // rendering rather than losing precision.
+                    None => visitor.visit_string(self.value.to_string()),
+                }
+            }
+            MapValue::String(s) => visitor.visit_str(s),
+            MapValue::Bytes(b) => visitor.visit_bytes(b),
+            MapValue::List(items) => visitor.visit_seq(SeqAccessImpl { iter: items.iter() }),
+            MapValue::Map(entries) => visitor.visit_map(MapAccessImpl {
+                iter: entries.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct SeqAccessImpl<'de> {
+    iter: std::slice::Iter<'de, MapValue>,
+}
+
+impl<'de> SeqAccess<'de> for SeqAccessImpl<'de> {
+    type Error = MapError;
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, MapError> {
+        match self.iter.next() {
+            Some(v) => seed.deserialize(ValueDeserializer { value: v }).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapAccessImpl<'de> {
+    iter: std::slice::Iter<'de, (String, MapValue)>,
+    value: Option<&'de MapValue>,
+}
+
+impl<'de> MapAccess<'de> for MapAccessImpl<'de> {
+    type Error = MapError;
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, MapError> {
+        match self.iter.next() {
+            Some((k, v)) => {
+                self.value = Some(v);
+                seed.deserialize(StrDeserializer::new(k)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, MapError> {
+        let v = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(ValueDeserializer { value: v })
+    }
+}
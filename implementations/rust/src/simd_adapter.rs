@@ -0,0 +1,129 @@
+//! `simd-json` tape backend for JSON-STRICT parsing (feature `simd`).
+//!
+//! `simd_json::to_tape` parses into a flat `Vec<Node>` — `Array`/`Object`
+//! carry a `len`/`count` used to know how many following tape slots belong
+//! to them, scalars are `Static`/`String` leaves. This walks that tape
+//! into the same `ParsedJson` tree `json_to_canon_value` already knows how
+//! to turn into a `MapValue`, so `json_strict_parse_with_dups` can swap
+//! backends without the rest of the pipeline noticing: integer-vs-float
+//! discrimination comes straight from the node variant instead of the
+//! serde_json `arbitrary_precision` magic-key hack (`Static::F64` is
+//! `ERR_TYPE` exactly like a token containing '.'/'e' is on the serde_json
+//! path; `Static::I64`/`Static::U64` become `ParsedJson::Number` tokens and
+//! flow through the existing i64 range check in `json_to_canon_value`).
+//!
+//! `simd-json` requires a mutable buffer it parses in place, so this takes
+//! a copy of `raw` rather than parsing the caller's slice directly.
+//!
+//! One documented divergence from the default `serde_json` backend:
+//! `simd-json`'s tape has no arbitrary-precision integer node — any JSON
+//! number literal outside `i64`/`u64` range (the exact case the default
+//! backend's `BigInt` promotion exists for) tape-encodes as a plain
+//! `Static::F64`, and the tape doesn't retain the original source digits
+//! anywhere else to recover them from: `simd_json::to_tape` has already
+//! discarded the token by the time we see `Node::Static`. So with this
+//! feature enabled, an out-of-range JSON integer is rejected as `ERR_TYPE`
+//! (the same as a literal float) instead of being promoted to
+//! `MapValue::BigInt`, which the default backend does. A document that
+//! relies on BigInt promotion will produce a valid MID without `simd` and
+//! `ERR_TYPE` with it — pick one backend per deployment rather than
+//! expecting both to agree on such documents.
+
+use simd_json::{Node, StaticNode};
+
+use crate::errors::*;
+use crate::json_adapter::{check_duplicates, ParsedJson};
+
+/// Parse `raw` via `simd-json`'s tape and return `(parsed_value, dup_found)`,
+/// matching `json_strict_parse_with_dups`'s contract exactly (the caller has
+/// already run `prescan_json_bytes` for size/BOM/UTF-8/surrogate checks).
+pub(crate) fn parse_tape_with_dups(raw: &[u8]) -> Result<(ParsedJson, bool), MapError> {
+    let mut buf = raw.to_vec();
+    let tape = simd_json::to_tape(&mut buf)
+        .map_err(|e| MapError::new(ERR_CANON_MCF, format!("JSON parse error: {}", e)))?;
+    let nodes = tape.as_ref();
+
+    let mut idx = 0;
+    let parsed = node_to_parsed(nodes, &mut idx)?;
+    if idx != nodes.len() {
+        return Err(MapError::new(ERR_CANON_MCF, "trailing tape nodes after root value"));
+    }
+
+    let mut dup_found = false;
+    check_duplicates(&parsed, &mut dup_found)?;
+    Ok((parsed, dup_found))
+}
+
+/// Consume one value (scalar or container) starting at `nodes[*idx]`,
+/// advancing `*idx` past it, and return the equivalent `ParsedJson`.
+fn node_to_parsed(nodes: &[Node], idx: &mut usize) -> Result<ParsedJson, MapError> {
+    if *idx >= nodes.len() {
+        return Err(MapError::new(ERR_CANON_MCF, "truncated tape"));
+    }
+
+    match &nodes[*idx] {
+        Node::Static(StaticNode::Null) => {
+            *idx += 1;
+            Ok(ParsedJson::Null)
+        }
+        Node::Static(StaticNode::Bool(b)) => {
+            let b = *b;
+            *idx += 1;
+            Ok(ParsedJson::Bool(b))
+        }
+        Node::Static(StaticNode::I64(n)) => {
+            let n = *n;
+            *idx += 1;
+            Ok(ParsedJson::Number(n.to_string()))
+        }
+        Node::Static(StaticNode::U64(n)) => {
+            let n = *n;
+            *idx += 1;
+            Ok(ParsedJson::Number(n.to_string()))
+        }
+        Node::Static(StaticNode::F64(n)) => {
+            // §8.2.1: floats are always ERR_TYPE — match the serde_json
+            // path's behavior on a token containing '.'/'e'/'E' rather than
+            // letting simd-json's float formatting leak into the error.
+            //
+            // This arm also catches out-of-i64/u64-range integer literals:
+            // simd-json tape-encodes those as F64 too (see this module's
+            // doc comment), so unlike the serde_json + RawValue backend
+            // they can't be promoted to `MapValue::BigInt` here — the
+            // original digits are already gone by this point.
+            let _ = n;
+            Err(MapError::new(ERR_TYPE, "JSON float not allowed"))
+        }
+        Node::String(s) => {
+            let s = s.to_string();
+            *idx += 1;
+            Ok(ParsedJson::String(s))
+        }
+        Node::Array { len, .. } => {
+            let len = *len;
+            *idx += 1;
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(node_to_parsed(nodes, idx)?);
+            }
+            Ok(ParsedJson::Array(items))
+        }
+        Node::Object { len, .. } => {
+            let len = *len;
+            *idx += 1;
+            let mut pairs = Vec::with_capacity(len);
+            for _ in 0..len {
+                let key = match &nodes[*idx] {
+                    Node::String(s) => s.to_string(),
+                    _ => {
+                        return Err(MapError::new(ERR_SCHEMA, "object key must be a string"));
+                    }
+                };
+                *idx += 1;
+                let value = node_to_parsed(nodes, idx)?;
+                pairs.push((key, value));
+            }
+            Ok(ParsedJson::Object(pairs))
+        }
+    }
+}
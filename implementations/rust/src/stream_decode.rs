@@ -0,0 +1,505 @@
+//! Incremental MCF decoder — reconstructs a `MapValue` from bytes fed in
+//! arbitrary chunks (sockets, `Read` loops, anything that can't guarantee
+//! a read lands on a value boundary).
+//!
+//! `mcf_decode_validate` (see `decode`) assumes the whole buffer is already
+//! in memory and recurses one call frame per nesting level.  `McfDecoder`
+//! instead keeps an explicit stack of open LIST/MAP frames and a small
+//! state machine for the value currently being read, so:
+//!   - a split landing inside a tag, a 4-byte length/count prefix, or a
+//!     payload just leaves the partial bytes buffered until more arrive;
+//!   - depth is `frames.len()`, checked against `MAX_DEPTH` on every push,
+//!     so adversarially deep input fails with `ERR_LIMIT_DEPTH` instead of
+//!     overflowing the real call stack.
+//!
+//! All invariants from `mcf_decode_validate` still apply: UTF-8/surrogate
+//! rejection, strict ascending unique map keys, depth and entry-count
+//! limits, and "exactly one root value, nothing after it".
+//!
+//! ## Error precedence
+//!
+//! `errors::choose_reported_error` exists to pick the highest-§6.2-
+//! precedence code when a document has more than one violation, but
+//! nothing here calls it — `step()` reports whatever violation it hits
+//! first, the same as `mcf_decode_validate` (which has the same property
+//! despite holding the whole buffer in memory: it's a left-to-right
+//! `?`-chained walk, not an accumulate-then-choose one). For this decoder
+//! that's not just a shortcut: bytes are validated strictly in wire
+//! order regardless of how they're chunked across `feed()` calls, so an
+//! earlier-positioned lower-precedence violation (say, a LIST's count
+//! prefix exceeding `MAX_LIST_ENTRIES`) is always discovered before a
+//! later-positioned higher-precedence one (say, a duplicate key further
+//! into the document) — true precedence ordering would mean not
+//! reporting anything until the entire document has been seen, which
+//! defeats the point of a decoder built to run in bounded memory against
+//! a stream that may never be fully buffered (a socket, an unbounded
+//! pipe). First-error-wins here is the same trade-off `mcf_decode_validate`
+//! already makes for a fully-buffered input; this type just can't do any
+//! better even when more bytes exist somewhere upstream.
+
+use crate::constants::*;
+use crate::encode::validate_utf8_scalar_bytes;
+use crate::errors::*;
+use crate::value::MapValue;
+
+/// Result of feeding bytes to a `McfDecoder`.
+#[derive(Debug)]
+pub enum Decode {
+    /// The root value is fully decoded; no further bytes should follow.
+    Done(MapValue),
+    /// Not enough bytes yet — feed more and call `feed` again.
+    NeedMore,
+}
+
+/// State of the value currently being read, independent of its nesting
+/// level (the nesting level lives in `McfDecoder::frames`).
+enum PartialValue {
+    /// Waiting for the 1-byte type tag.
+    Tag,
+    /// Tag read; waiting for the 4-byte uint32be length or count.
+    Len(u8),
+    /// STRING payload: target length and bytes accumulated so far.
+    StringPayload(usize, Vec<u8>),
+    /// BYTES payload: target length and bytes accumulated so far.
+    BytesPayload(usize, Vec<u8>),
+    /// BOOLEAN payload: single byte, must be 0x00 or 0x01.
+    BoolPayload,
+    /// INTEGER payload: 8 big-endian bytes accumulated so far.
+    IntPayload(Vec<u8>),
+    /// BIGINT sign byte not yet read.
+    BigIntSign,
+    /// Sign read; accumulating the LEB128 length varint.
+    BigIntLen { negative: bool, value: u64, shift: u32 },
+    /// Length known; accumulating magnitude bytes.
+    BigIntPayload { negative: bool, len: usize, acc: Vec<u8> },
+}
+
+/// What a just-completed scalar value means to the decoder: a MAP key
+/// (only STRING is legal there) or an ordinary value.
+enum Completed {
+    String(String),
+    Value(MapValue),
+}
+
+/// An open LIST or MAP container, holding its partially-built children.
+enum Frame {
+    List {
+        remaining: u32,
+        items: Vec<MapValue>,
+    },
+    Map {
+        remaining: u32,
+        entries: Vec<(String, MapValue)>,
+        /// Raw bytes of the last accepted key, for ordering/dup checks (§3.5, §3.6).
+        prev_key: Option<Vec<u8>>,
+        /// Key already read for the entry currently in progress, awaiting its value.
+        pending_key: Option<String>,
+    },
+}
+
+/// Streaming, resumable MCF decoder.
+///
+/// Feed it bytes via [`feed`](McfDecoder::feed) as they arrive, in any
+/// chunking. Once the root value is complete, `feed` returns
+/// `Decode::Done`; any bytes fed after that (or left over in the same
+/// call) are trailing bytes and raise `ERR_CANON_MCF`.
+pub struct McfDecoder {
+    frames: Vec<Frame>,
+    cur: PartialValue,
+    buf: Vec<u8>,
+    pos: usize,
+    root: Option<MapValue>,
+    finished: bool,
+}
+
+impl Default for McfDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McfDecoder {
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            cur: PartialValue::Tag,
+            buf: Vec::new(),
+            pos: 0,
+            root: None,
+            finished: false,
+        }
+    }
+
+    /// Feed the next chunk of MCF bytes (may be any length, including zero).
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Decode, MapError> {
+        if self.finished {
+            if chunk.is_empty() {
+                return Ok(Decode::NeedMore);
+            }
+            return Err(MapError::new(ERR_CANON_MCF, "trailing bytes after MCF root"));
+        }
+
+        self.buf.extend_from_slice(chunk);
+        while self.root.is_none() {
+            if !self.step()? {
+                break;
+            }
+        }
+
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+
+        if let Some(root) = self.root.take() {
+            if !self.buf.is_empty() {
+                return Err(MapError::new(ERR_CANON_MCF, "trailing bytes after MCF root"));
+            }
+            self.finished = true;
+            Ok(Decode::Done(root))
+        } else {
+            Ok(Decode::NeedMore)
+        }
+    }
+
+    /// Are we currently waiting for a MAP key (as opposed to a value)?
+    fn expecting_map_key(&self) -> bool {
+        matches!(self.frames.last(), Some(Frame::Map { pending_key: None, .. }))
+    }
+
+    /// Try to make one unit of progress. Returns `Ok(false)` when the
+    /// buffered bytes are exhausted and more input is needed.
+    fn step(&mut self) -> Result<bool, MapError> {
+        let cur = std::mem::replace(&mut self.cur, PartialValue::Tag);
+        match cur {
+            PartialValue::Tag => {
+                if self.pos >= self.buf.len() {
+                    self.cur = PartialValue::Tag;
+                    return Ok(false);
+                }
+                let tag = self.buf[self.pos];
+                self.pos += 1;
+                if self.expecting_map_key() && tag != TAG_STRING {
+                    return Err(MapError::new(ERR_SCHEMA, "map key must be STRING"));
+                }
+                match tag {
+                    TAG_STRING | TAG_BYTES | TAG_LIST | TAG_MAP => {
+                        self.cur = PartialValue::Len(tag);
+                    }
+                    TAG_BOOLEAN => self.cur = PartialValue::BoolPayload,
+                    TAG_INTEGER => self.cur = PartialValue::IntPayload(Vec::with_capacity(8)),
+                    TAG_BIGINT => self.cur = PartialValue::BigIntSign,
+                    other => {
+                        return Err(MapError::new(
+                            ERR_CANON_MCF,
+                            format!("unknown MCF tag 0x{:02x}", other),
+                        ));
+                    }
+                }
+                Ok(true)
+            }
+
+            PartialValue::Len(tag) => {
+                if self.pos + 4 > self.buf.len() {
+                    self.cur = PartialValue::Len(tag);
+                    return Ok(false);
+                }
+                let n = u32::from_be_bytes([
+                    self.buf[self.pos],
+                    self.buf[self.pos + 1],
+                    self.buf[self.pos + 2],
+                    self.buf[self.pos + 3],
+                ]);
+                self.pos += 4;
+                match tag {
+                    TAG_STRING => {
+                        // Defense-in-depth: a single payload longer than the
+                        // whole permitted CANON_BYTES can never be valid, so
+                        // reject before buffering an adversarial allocation.
+                        if n as usize > MAX_CANON_BYTES {
+                            return Err(MapError::new(
+                                ERR_LIMIT_SIZE,
+                                "string length exceeds MAX_CANON_BYTES",
+                            ));
+                        }
+                        self.cur = PartialValue::StringPayload(n as usize, Vec::new());
+                        Ok(true)
+                    }
+                    TAG_BYTES => {
+                        if n as usize > MAX_CANON_BYTES {
+                            return Err(MapError::new(
+                                ERR_LIMIT_SIZE,
+                                "bytes length exceeds MAX_CANON_BYTES",
+                            ));
+                        }
+                        self.cur = PartialValue::BytesPayload(n as usize, Vec::new());
+                        Ok(true)
+                    }
+                    TAG_LIST => {
+                        if n > MAX_LIST_ENTRIES {
+                            return Err(MapError::new(
+                                ERR_LIMIT_SIZE,
+                                "list entry count exceeds limit",
+                            ));
+                        }
+                        self.push_container(Frame::List {
+                            remaining: n,
+                            items: Vec::new(),
+                        })?;
+                        Ok(true)
+                    }
+                    TAG_MAP => {
+                        if n > MAX_MAP_ENTRIES {
+                            return Err(MapError::new(
+                                ERR_LIMIT_SIZE,
+                                "map entry count exceeds limit",
+                            ));
+                        }
+                        self.push_container(Frame::Map {
+                            remaining: n,
+                            entries: Vec::new(),
+                            prev_key: None,
+                            pending_key: None,
+                        })?;
+                        Ok(true)
+                    }
+                    _ => unreachable!("Len state only reached for STRING/BYTES/LIST/MAP"),
+                }
+            }
+
+            PartialValue::StringPayload(len, mut acc) => {
+                let take = (len - acc.len()).min(self.buf.len() - self.pos);
+                acc.extend_from_slice(&self.buf[self.pos..self.pos + take]);
+                self.pos += take;
+                if acc.len() < len {
+                    self.cur = PartialValue::StringPayload(len, acc);
+                    return Ok(take > 0);
+                }
+                validate_utf8_scalar_bytes(&acc)?;
+                let s = String::from_utf8(acc).expect("validated above");
+                self.on_value_complete(Completed::String(s))?;
+                Ok(true)
+            }
+
+            PartialValue::BytesPayload(len, mut acc) => {
+                let take = (len - acc.len()).min(self.buf.len() - self.pos);
+                acc.extend_from_slice(&self.buf[self.pos..self.pos + take]);
+                self.pos += take;
+                if acc.len() < len {
+                    self.cur = PartialValue::BytesPayload(len, acc);
+                    return Ok(take > 0);
+                }
+                self.on_value_complete(Completed::Value(MapValue::Bytes(acc)))?;
+                Ok(true)
+            }
+
+            PartialValue::BoolPayload => {
+                if self.pos >= self.buf.len() {
+                    self.cur = PartialValue::BoolPayload;
+                    return Ok(false);
+                }
+                let payload = self.buf[self.pos];
+                self.pos += 1;
+                if payload != 0x00 && payload != 0x01 {
+                    return Err(MapError::new(
+                        ERR_CANON_MCF,
+                        format!("invalid boolean payload 0x{:02x}", payload),
+                    ));
+                }
+                self.on_value_complete(Completed::Value(MapValue::Boolean(payload == 0x01)))?;
+                Ok(true)
+            }
+
+            PartialValue::IntPayload(mut acc) => {
+                let take = (8 - acc.len()).min(self.buf.len() - self.pos);
+                acc.extend_from_slice(&self.buf[self.pos..self.pos + take]);
+                self.pos += take;
+                if acc.len() < 8 {
+                    self.cur = PartialValue::IntPayload(acc);
+                    return Ok(take > 0);
+                }
+                let mut b = [0u8; 8];
+                b.copy_from_slice(&acc);
+                self.on_value_complete(Completed::Value(MapValue::Integer(i64::from_be_bytes(b))))?;
+                Ok(true)
+            }
+
+            PartialValue::BigIntSign => {
+                if self.pos >= self.buf.len() {
+                    self.cur = PartialValue::BigIntSign;
+                    return Ok(false);
+                }
+                let sign = self.buf[self.pos];
+                self.pos += 1;
+                if sign != 0x00 && sign != 0x01 {
+                    return Err(MapError::new(
+                        ERR_CANON_MCF,
+                        format!("invalid bigint sign byte 0x{:02x}", sign),
+                    ));
+                }
+                self.cur = PartialValue::BigIntLen {
+                    negative: sign == 0x01,
+                    value: 0,
+                    shift: 0,
+                };
+                Ok(true)
+            }
+
+            PartialValue::BigIntLen { negative, value, shift } => {
+                if self.pos >= self.buf.len() {
+                    self.cur = PartialValue::BigIntLen { negative, value, shift };
+                    return Ok(false);
+                }
+                let byte = self.buf[self.pos];
+                self.pos += 1;
+                if shift >= 64 {
+                    return Err(MapError::new(ERR_CANON_MCF, "bigint length varint too long"));
+                }
+                let value = value | (((byte & 0x7f) as u64) << shift);
+                if byte == 0 && shift > 0 {
+                    return Err(MapError::new(ERR_CANON_MCF, "non-minimal bigint length varint"));
+                }
+                if byte & 0x80 != 0 {
+                    self.cur = PartialValue::BigIntLen {
+                        negative,
+                        value,
+                        shift: shift + 7,
+                    };
+                    return Ok(true);
+                }
+                let len = value as usize;
+                if len > MAX_CANON_BYTES {
+                    return Err(MapError::new(
+                        ERR_LIMIT_SIZE,
+                        "bigint magnitude length exceeds MAX_CANON_BYTES",
+                    ));
+                }
+                self.cur = PartialValue::BigIntPayload {
+                    negative,
+                    len,
+                    acc: Vec::new(),
+                };
+                Ok(true)
+            }
+
+            PartialValue::BigIntPayload { negative, len, mut acc } => {
+                let take = (len - acc.len()).min(self.buf.len() - self.pos);
+                acc.extend_from_slice(&self.buf[self.pos..self.pos + take]);
+                self.pos += take;
+                if acc.len() < len {
+                    self.cur = PartialValue::BigIntPayload { negative, len, acc };
+                    return Ok(take > 0);
+                }
+                if len == 0 && negative {
+                    return Err(MapError::new(ERR_CANON_MCF, "negative-zero BigInt"));
+                }
+                if len > 0 && acc[0] == 0 {
+                    return Err(MapError::new(ERR_CANON_MCF, "non-minimal BigInt magnitude"));
+                }
+                self.on_value_complete(Completed::Value(MapValue::BigInt(negative, acc)))?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Push a newly-opened LIST/MAP frame, enforcing `MAX_DEPTH` and
+    /// completing it immediately if it has zero entries.
+    fn push_container(&mut self, frame: Frame) -> Result<(), MapError> {
+        if self.frames.len() as u32 + 1 > MAX_DEPTH {
+            return Err(MapError::new(ERR_LIMIT_DEPTH, "depth exceeds MAX_DEPTH"));
+        }
+        let empty = match &frame {
+            Frame::List { remaining, .. } => *remaining == 0,
+            Frame::Map { remaining, .. } => *remaining == 0,
+        };
+        if empty {
+            let value = match frame {
+                Frame::List { .. } => MapValue::List(Vec::new()),
+                Frame::Map { .. } => MapValue::Map(Vec::new()),
+            };
+            self.bubble_value(value)
+        } else {
+            self.frames.push(frame);
+            Ok(())
+        }
+    }
+
+    /// Route a just-completed scalar to its role: a MAP key, or a value
+    /// to fold into the enclosing container (or the root).
+    fn on_value_complete(&mut self, kind: Completed) -> Result<(), MapError> {
+        if self.expecting_map_key() {
+            let key = match kind {
+                Completed::String(s) => s,
+                Completed::Value(_) => unreachable!("tag check enforces STRING for map keys"),
+            };
+            return self.set_pending_key(key);
+        }
+        let value = match kind {
+            Completed::String(s) => MapValue::String(s),
+            Completed::Value(v) => v,
+        };
+        self.bubble_value(value)
+    }
+
+    /// Record a decoded MAP key: check ordering/uniqueness against the
+    /// previous key (§3.5, §3.6), then wait for its value.
+    fn set_pending_key(&mut self, key: String) -> Result<(), MapError> {
+        match self.frames.last_mut() {
+            Some(Frame::Map { prev_key, pending_key, .. }) => {
+                let key_bytes = key.as_bytes();
+                if let Some(prev) = prev_key {
+                    match prev.as_slice().cmp(key_bytes) {
+                        std::cmp::Ordering::Equal => {
+                            return Err(MapError::new(ERR_DUP_KEY, "duplicate key in MCF"));
+                        }
+                        std::cmp::Ordering::Greater => {
+                            return Err(MapError::new(ERR_KEY_ORDER, "key order violation in MCF"));
+                        }
+                        std::cmp::Ordering::Less => {}
+                    }
+                }
+                *prev_key = Some(key_bytes.to_vec());
+                *pending_key = Some(key);
+                Ok(())
+            }
+            _ => unreachable!("set_pending_key only called while expecting_map_key"),
+        }
+    }
+
+    /// Fold a completed value into its parent frame (or finish the root),
+    /// recursing upward whenever that completes the parent in turn.
+    fn bubble_value(&mut self, value: MapValue) -> Result<(), MapError> {
+        match self.frames.pop() {
+            None => {
+                self.root = Some(value);
+                Ok(())
+            }
+            Some(Frame::List { remaining, mut items }) => {
+                items.push(value);
+                let remaining = remaining - 1;
+                if remaining == 0 {
+                    self.bubble_value(MapValue::List(items))
+                } else {
+                    self.frames.push(Frame::List { remaining, items });
+                    Ok(())
+                }
+            }
+            Some(Frame::Map { remaining, mut entries, prev_key, mut pending_key }) => {
+                let key = pending_key.take().expect("value completed without a pending key");
+                entries.push((key, value));
+                let remaining = remaining - 1;
+                if remaining == 0 {
+                    self.bubble_value(MapValue::Map(entries))
+                } else {
+                    self.frames.push(Frame::Map {
+                        remaining,
+                        entries,
+                        prev_key,
+                        pending_key,
+                    });
+                    Ok(())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Pluggable string-profile validation (§3.4) beyond the default
+//! surrogate-only check.
+//!
+//! `validate_utf8_scalar` only rejects surrogates. Stricter deployments
+//! may additionally want to forbid C0/C1 control characters, Unicode
+//! noncharacters (U+FDD0-U+FDEF and the U+nFFFE/U+nFFFF pair on every
+//! plane), and private-use areas, still raising `ERR_UTF8`.
+//! `StringProfile` holds a sorted table of disallowed `(start, end)`
+//! inclusive code-point ranges and checks membership with a binary
+//! search, so the hot "everything allowed" path costs one `O(log n)`
+//! failed search rather than a chain of `if`s that grows with every
+//! additional forbidden class.
+
+use crate::errors::*;
+use crate::value::MapValue;
+
+/// An inclusive code-point range, e.g. `(0xFDD0, 0xFDEF)`.
+type Range = (u32, u32);
+
+/// A set of forbidden code-point classes, checked at encode time in place
+/// of (or in addition to) the default surrogate-only check.
+#[derive(Debug, Clone)]
+pub struct StringProfile {
+    /// Disallowed ranges, sorted ascending by `start` and non-overlapping.
+    forbidden: Vec<Range>,
+}
+
+impl StringProfile {
+    /// The default profile: surrogates only (U+D800-U+DFFF). Matches
+    /// `validate_utf8_scalar` exactly, so existing encode behavior is
+    /// unchanged unless a stricter profile is opted into.
+    pub fn surrogates_only() -> Self {
+        Self {
+            forbidden: vec![(0xD800, 0xDFFF)],
+        }
+    }
+
+    /// Surrogates, plus C0/C1 controls, noncharacters, and private-use
+    /// areas. Opt-in for deployments that want a stricter canonical text
+    /// policy.
+    pub fn strict() -> Self {
+        let mut forbidden = vec![
+            (0x0000, 0x001F),     // C0 controls
+            (0x007F, 0x009F),     // DEL + C1 controls
+            (0xD800, 0xDFFF),     // surrogates
+            (0xE000, 0xF8FF),     // BMP private-use area
+            (0xFDD0, 0xFDEF),     // noncharacters
+            (0xF0000, 0xFFFFD),   // supplementary private-use area A
+            (0x100000, 0x10FFFD), // supplementary private-use area B
+        ];
+        // Per-plane noncharacters U+nFFFE/U+nFFFF for every plane 0..=0x10.
+        for plane in 0..=0x10u32 {
+            let base = plane << 16;
+            forbidden.push((base | 0xFFFE, base | 0xFFFF));
+        }
+        forbidden.sort_by_key(|&(start, _)| start);
+        Self { forbidden }
+    }
+
+    /// Reject if `s` contains any code point in a forbidden range.
+    pub fn validate(&self, s: &str) -> Result<(), MapError> {
+        for ch in s.chars() {
+            let cp = ch as u32;
+            if self.contains(cp) {
+                return Err(MapError::new(
+                    ERR_UTF8,
+                    format!("disallowed code-point U+{:04X}", cp),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// `O(log n)` membership check: find the partition point where
+    /// `start <= cp`, then check whether that candidate range's `end`
+    /// still covers `cp`.
+    fn contains(&self, cp: u32) -> bool {
+        let idx = self.forbidden.partition_point(|&(start, _)| start <= cp);
+        if idx == 0 {
+            return false;
+        }
+        let (_, end) = self.forbidden[idx - 1];
+        cp <= end
+    }
+}
+
+impl Default for StringProfile {
+    fn default() -> Self {
+        Self::surrogates_only()
+    }
+}
+
+/// Validate every STRING in `val`, including MAP keys, against `profile`,
+/// recursing through LIST/MAP containers.
+///
+/// Run this ahead of `mcf_encode_value` to apply a profile stricter than
+/// the default surrogate-only check; scalars other than STRING carry no
+/// text to validate.
+pub fn validate_value_profile(val: &MapValue, profile: &StringProfile) -> Result<(), MapError> {
+    match val {
+        MapValue::String(s) => profile.validate(s),
+        MapValue::Bytes(_) | MapValue::Boolean(_) | MapValue::Integer(_) | MapValue::BigInt(..) => Ok(()),
+        MapValue::List(items) => {
+            for item in items {
+                validate_value_profile(item, profile)?;
+            }
+            Ok(())
+        }
+        MapValue::Map(entries) => {
+            for (k, v) in entries {
+                profile.validate(k)?;
+                validate_value_profile(v, profile)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -30,6 +30,118 @@ pub enum MapValue {
     Boolean(bool),
     /// Signed 64-bit integer (v1.1).  Distinct from STRING representation.
     Integer(i64),
+    /// Arbitrary-precision integer (v1.1 extension): `(negative, magnitude)`
+    /// where `magnitude` is minimal big-endian bytes (no leading zero byte)
+    /// and `negative` is `false` for zero. Used for integers too large for
+    /// `Integer`'s `i64` — token amounts, large IDs, crypto values — so
+    /// they don't have to be rejected or silently truncated. Distinct from
+    /// `Integer` the same way `Integer` is already distinct from STRING:
+    /// `BigInt(false, [])` and `Integer(0)` both mean zero but encode (and
+    /// therefore hash) differently. Construct via [`MapValue::big_int_from_decimal`]
+    /// or [`MapValue::big_int_from_u64`] rather than building the tuple by
+    /// hand, to avoid an accidental non-minimal encoding that
+    /// `mcf_encode_to`/`mcf_decode_validate` would reject.
+    BigInt(bool, Vec<u8>),
+}
+
+impl MapValue {
+    /// Parse a decimal digit string (optionally `-`-prefixed) into a
+    /// `BigInt`, via repeated multiply-by-10 on a base-256 accumulator —
+    /// the same technique arbitrary-precision libraries use to read a
+    /// decimal literal without going through a fixed-width integer type.
+    /// Returns `None` if `token` isn't `-?[0-9]+`.
+    pub fn big_int_from_decimal(token: &str) -> Option<MapValue> {
+        let (negative, digits) = match token.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, token),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let magnitude = decimal_digits_to_magnitude(digits);
+        // "-0" is mathematically zero, which has no sign.
+        let negative = negative && !magnitude.is_empty();
+        Some(MapValue::BigInt(negative, magnitude))
+    }
+
+    /// Build a non-negative `BigInt` from a `u64`, with minimal big-endian
+    /// magnitude bytes (empty for zero).
+    pub fn big_int_from_u64(v: u64) -> MapValue {
+        let bytes = v.to_be_bytes();
+        match bytes.iter().position(|&b| b != 0) {
+            Some(first) => MapValue::BigInt(false, bytes[first..].to_vec()),
+            None => MapValue::BigInt(false, Vec::new()),
+        }
+    }
+}
+
+/// Convert a decimal digit string (no sign) into minimal big-endian
+/// magnitude bytes.
+fn decimal_digits_to_magnitude(digits: &str) -> Vec<u8> {
+    let mut magnitude: Vec<u8> = Vec::new();
+    for ch in digits.bytes() {
+        let digit = (ch - b'0') as u32;
+        let mut carry = digit;
+        for byte in magnitude.iter_mut().rev() {
+            let acc = *byte as u32 * 10 + carry;
+            *byte = (acc & 0xff) as u8;
+            carry = acc >> 8;
+        }
+        while carry > 0 {
+            magnitude.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    magnitude
+}
+
+/// Convert big-endian magnitude bytes (base 256) to a decimal digit
+/// string, via repeated divide-by-10 — the inverse of
+/// `decimal_digits_to_magnitude`.
+fn magnitude_to_decimal(magnitude: &[u8]) -> String {
+    if magnitude.is_empty() {
+        return "0".to_string();
+    }
+    let mut digits = magnitude.to_vec();
+    let mut out = Vec::new();
+    while digits.iter().any(|&b| b != 0) {
+        let mut remainder: u32 = 0;
+        for byte in digits.iter_mut() {
+            let acc = remainder * 256 + *byte as u32;
+            *byte = (acc / 10) as u8;
+            remainder = acc % 10;
+        }
+        out.push(b'0' + remainder as u8);
+    }
+    out.reverse();
+    String::from_utf8(out).expect("decimal digits are ASCII")
+}
+
+/// Convert `BigInt` fields to an `i128`, if the value fits — used by the
+/// serde bridge, which can represent anything up to `i128` natively
+/// without falling back to a decimal string.
+pub(crate) fn big_int_to_i128(negative: bool, magnitude: &[u8]) -> Option<i128> {
+    if magnitude.len() > 16 {
+        return None;
+    }
+    let mut val: u128 = 0;
+    for &b in magnitude {
+        val = (val << 8) | b as u128;
+    }
+    if negative {
+        if val == 1u128 << 127 {
+            return Some(i128::MIN);
+        }
+        if val > i128::MAX as u128 {
+            return None;
+        }
+        Some(-(val as i128))
+    } else {
+        if val > i128::MAX as u128 {
+            return None;
+        }
+        Some(val as i128)
+    }
 }
 
 impl fmt::Display for MapValue {
@@ -41,6 +153,13 @@ impl fmt::Display for MapValue {
             MapValue::Map(entries) => write!(f, "{{{} entries}}", entries.len()),
             MapValue::Boolean(b) => write!(f, "{}", b),
             MapValue::Integer(i) => write!(f, "{}", i),
+            MapValue::BigInt(negative, magnitude) => {
+                if *negative {
+                    write!(f, "-{}", magnitude_to_decimal(magnitude))
+                } else {
+                    write!(f, "{}", magnitude_to_decimal(magnitude))
+                }
+            }
         }
     }
 }
@@ -176,6 +176,44 @@ fn json_integer_accepted() {
     assert!(mid.starts_with("map1:"));
 }
 
+#[test]
+fn json_negative_zero_accepted() {
+    // "-0" has no '.'/'e'/'E' and fits i64 (as 0), so it's a valid
+    // INTEGER — pins that RawValue-captured source text round-trips
+    // through the i64 parse the same as any other token.
+    let mid = mid_full_json(br#"{"v":-0}"#).unwrap();
+    assert_eq!(mid, mid_full_json(br#"{"v":0}"#).unwrap());
+}
+
+#[test]
+fn json_leading_zero_rejected() {
+    // "01" isn't a valid JSON number (RFC 8259 forbids leading zeros),
+    // so this is rejected as a parse error before it ever reaches our
+    // own integer-vs-float check.
+    let err = mid_full_json(br#"{"v":01}"#).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+}
+
+#[test]
+fn json_oversized_integer_promoted_to_bigint() {
+    // Exceeds i64::MAX but is still a syntactically valid JSON integer;
+    // pins that the raw token is preserved exactly, so the overflow is
+    // promoted to a BIGINT rather than rounded through f64.
+    let mid = mid_full_json(br#"{"v":99999999999999999999999999}"#).unwrap();
+    let val = MapValue::Map(vec![(
+        "v".into(),
+        MapValue::big_int_from_decimal("99999999999999999999999999").unwrap(),
+    )]);
+    assert_eq!(mid, mid_full(&val).unwrap());
+}
+
+#[test]
+fn json_parse_error_carries_position() {
+    let err = mid_full_json(br#"{"a": 1, "b": }"#).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+    assert!(err.position.is_some());
+}
+
 #[test]
 fn json_bom_rejected() {
     let mut input = vec![0xEF, 0xBB, 0xBF]; // UTF-8 BOM
@@ -184,7 +222,788 @@ fn json_bom_rejected() {
     assert_eq!(err.code, ERR_SCHEMA);
 }
 
+#[test]
+fn canon_value_to_json_round_trips_mid() {
+    let descriptor = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("count".into(), MapValue::Integer(3)),
+        ("ok".into(), MapValue::Boolean(true)),
+        ("tags".into(), MapValue::List(vec![MapValue::String("a".into())])),
+    ]);
+    let json = canon_value_to_json(&descriptor);
+    let mid = mid_full_json(json.as_bytes()).unwrap();
+    assert_eq!(mid, mid_full(&descriptor).unwrap());
+}
+
+#[test]
+fn canon_value_to_json_escapes_bytes_with_sentinel() {
+    let descriptor = MapValue::Bytes(vec![0xDE, 0xAD, 0xBE, 0xEF]);
+    let json = canon_value_to_json(&descriptor);
+    assert!(json.contains(BYTES_JSON_SENTINEL));
+}
+
+// ── Streaming MID (v1.1 extension) ──────────────────────────
+
+#[test]
+fn mid_full_streaming_matches_mid_full() {
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("count".into(), MapValue::Integer(3)),
+    ]);
+    assert_eq!(mid_full(&val).unwrap(), mid_full_streaming(&val).unwrap());
+}
+
+#[test]
+fn mid_full_streaming_oversized_value_hits_limit_size() {
+    // A single string past MAX_CANON_BYTES pushes the running streamed
+    // count over the limit mid-encode, the same as the buffered path.
+    let huge = MapValue::Map(vec![("v".into(), MapValue::String("x".repeat(2_000_000)))]);
+    let err = mid_full_streaming(&huge).unwrap_err();
+    assert_eq!(err.code, ERR_LIMIT_SIZE);
+    let buffered_err = mid_full(&huge).unwrap_err();
+    assert_eq!(buffered_err.code, ERR_LIMIT_SIZE);
+}
+
+// ── Self-describing multihash MID (v1.1 extension) ─────────
+
+#[test]
+fn mid_multihash_round_trips_per_algo() {
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+    ]);
+    let canon = canonical_bytes_full(&val).unwrap();
+
+    for algo in [MidAlgo::Sha256, MidAlgo::Sha512, MidAlgo::Blake3] {
+        let mid = mid_multihash(&canon, algo);
+        assert!(mid.starts_with("map1:u"));
+        let parsed = parse_mid(&mid).unwrap();
+        assert_eq!(parsed.algo, algo);
+        assert!(!parsed.digest.is_empty());
+    }
+}
+
+#[test]
+fn mid_multihash_differs_across_algos() {
+    let canon = vec![1, 2, 3, 4];
+    let sha256 = mid_multihash(&canon, MidAlgo::Sha256);
+    let sha512 = mid_multihash(&canon, MidAlgo::Sha512);
+    let blake3 = mid_multihash(&canon, MidAlgo::Blake3);
+    assert_ne!(sha256, sha512);
+    assert_ne!(sha256, blake3);
+    assert_ne!(sha512, blake3);
+}
+
+#[test]
+fn parse_mid_rejects_default_hex_spelling() {
+    let val = MapValue::Map(vec![]);
+    let mid = mid_full(&val).unwrap();
+    let err = parse_mid(&mid).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA);
+}
+
+// ── Arbitrary-precision integer (v1.1 extension) ────────────
+
+#[test]
+fn bigint_round_trips_through_mcf_and_stream_decoder() {
+    for token in ["9223372036854775808", "18446744073709551616", "-9223372036854775809"] {
+        let val = MapValue::Map(vec![("n".into(), MapValue::big_int_from_decimal(token).unwrap())]);
+        let canon = canonical_bytes_full(&val).unwrap();
+        let mcf = &canon[map1::constants::CANON_HDR.len()..];
+
+        let mut decoder = McfDecoder::new();
+        let decoded = match decoder.feed(mcf).unwrap() {
+            Decode::Done(v) => v,
+            Decode::NeedMore => panic!("expected a complete value for {}", token),
+        };
+        match decoded {
+            MapValue::Map(entries) => {
+                assert_eq!(entries[0].1.to_string(), token);
+            }
+            other => panic!("expected a MAP, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn bigint_streaming_decode_byte_at_a_time() {
+    let val = MapValue::BigInt(false, vec![0xab; 40]);
+    let canon = canonical_bytes_full(&MapValue::Map(vec![("n".into(), val.clone())])).unwrap();
+    let mcf = &canon[map1::constants::CANON_HDR.len()..];
+
+    let mut decoder = McfDecoder::new();
+    let mut result = None;
+    for byte in mcf {
+        match decoder.feed(std::slice::from_ref(byte)).unwrap() {
+            Decode::Done(v) => result = Some(v),
+            Decode::NeedMore => {}
+        }
+    }
+    match result.unwrap() {
+        MapValue::Map(entries) => assert_eq!(entries[0].1, val),
+        other => panic!("expected a MAP, got {:?}", other),
+    }
+}
+
+#[test]
+fn bigint_rejects_non_minimal_magnitude() {
+    let val = MapValue::Map(vec![("n".into(), MapValue::BigInt(false, vec![0x00, 0x01]))]);
+    let err = canonical_bytes_full(&val).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+}
+
+#[test]
+fn bigint_rejects_negative_zero() {
+    let val = MapValue::Map(vec![("n".into(), MapValue::BigInt(true, vec![]))]);
+    let err = canonical_bytes_full(&val).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+}
+
+#[test]
+fn json_promotes_overflowing_integer_to_bigint() {
+    let json = br#"{"n": 18446744073709551616}"#;
+    let mid_a = mid_full_json(json).unwrap();
+    let val = MapValue::Map(vec![(
+        "n".into(),
+        MapValue::big_int_from_decimal("18446744073709551616").unwrap(),
+    )]);
+    let mid_b = mid_full(&val).unwrap();
+    assert_eq!(mid_a, mid_b);
+}
+
+/// Pins the documented `simd`/`BigInt` divergence (see `simd_adapter`'s
+/// module doc): `simd-json` tape-encodes an out-of-i64/u64-range integer
+/// literal as `Static::F64` with no way to recover the source digits, so
+/// this rejects as `ERR_TYPE` under `simd` instead of promoting to
+/// `MapValue::BigInt` the way `json_promotes_overflowing_integer_to_bigint`
+/// (the default-backend test above) does for the identical bytes.
+#[cfg(feature = "simd")]
+#[test]
+fn json_overflowing_integer_is_err_type_under_simd() {
+    let json = br#"{"n": 18446744073709551616}"#;
+    let err = mid_full_json(json).unwrap_err();
+    assert_eq!(err.code, ERR_TYPE);
+}
+
+#[test]
+fn json_round_trips_bigint_through_canon_value_to_json() {
+    let val = MapValue::Map(vec![(
+        "n".into(),
+        MapValue::big_int_from_decimal("-99999999999999999999999999999999999999").unwrap(),
+    )]);
+    let json = canon_value_to_json(&val);
+    assert!(json.contains("-99999999999999999999999999999999999999"));
+    assert_eq!(mid_full_json(json.as_bytes()).unwrap(), mid_full(&val).unwrap());
+}
+
+#[test]
+fn serde_bridge_promotes_overflowing_u64_to_bigint() {
+    let val = to_map_value(&u64::MAX).unwrap();
+    assert_eq!(val, MapValue::big_int_from_u64(u64::MAX));
+}
+
+// ── Inverse decode (v1.1 extension) ─────────────────────────
+
+#[test]
+fn value_from_canon_bytes_round_trips_arbitrary_value() {
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("big".into(), MapValue::big_int_from_decimal("18446744073709551616").unwrap()),
+        ("count".into(), MapValue::Integer(-3)),
+        ("enabled".into(), MapValue::Boolean(true)),
+        ("payload".into(), MapValue::Bytes(vec![1, 2, 3])),
+        (
+            "tags".into(),
+            MapValue::List(vec![MapValue::String("a".into()), MapValue::String("b".into())]),
+        ),
+    ]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let decoded = value_from_canon_bytes(&canon).unwrap();
+
+    assert_eq!(decoded, val);
+    let re_encoded = canonical_bytes_full_from_mcf_value(&decoded);
+    assert_eq!(re_encoded, canon);
+}
+
+#[test]
+fn value_from_canon_bytes_rejects_bad_header() {
+    let err = value_from_canon_bytes(b"NOPE\x0012345").unwrap_err();
+    assert_eq!(err.code, ERR_CANON_HDR);
+}
+
+#[test]
+fn value_from_canon_bytes_rejects_trailing_bytes() {
+    let val = MapValue::Map(vec![]);
+    let mut canon = canonical_bytes_full(&val).unwrap();
+    canon.push(0xff);
+    let err = value_from_canon_bytes(&canon).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+}
+
+#[test]
+fn value_from_canon_bytes_enforces_depth_limit() {
+    // Hand-built MCF: 40 nested single-entry MAPs around an empty MAP,
+    // past MAX_DEPTH (32) — the encoder itself refuses to produce bytes
+    // this deep, so this exercises the decoder's own depth check directly.
+    fn wrap_in_map(mut mcf: Vec<u8>) -> Vec<u8> {
+        let mut out = vec![0x04]; // TAG_MAP
+        out.extend_from_slice(&1u32.to_be_bytes()); // 1 entry
+        out.push(0x01); // TAG_STRING (key)
+        out.extend_from_slice(&1u32.to_be_bytes());
+        out.push(b'a');
+        out.append(&mut mcf);
+        out
+    }
+    let mut mcf = vec![0x04]; // empty TAG_MAP
+    mcf.extend_from_slice(&0u32.to_be_bytes());
+    for _ in 0..40 {
+        mcf = wrap_in_map(mcf);
+    }
+    let mut canon = map1::constants::CANON_HDR.to_vec();
+    canon.extend_from_slice(&mcf);
+
+    let err = value_from_canon_bytes(&canon).unwrap_err();
+    assert_eq!(err.code, ERR_LIMIT_DEPTH);
+}
+
+/// Re-encode a value that's already in canonical (sorted, FULL-projected)
+/// shape straight through `mcf_encode_to_writer`, a separate entry point
+/// from the `canonical_bytes_full` call that produced the original bytes.
+fn canonical_bytes_full_from_mcf_value(val: &MapValue) -> Vec<u8> {
+    let mut canon = map1::constants::CANON_HDR.to_vec();
+    map1::encode::mcf_encode_to_writer(val, &mut canon, 0).unwrap();
+    canon
+}
+
 #[test]
 fn spec_version_correct() {
     assert_eq!(SPEC_VERSION, "1.1");
 }
+
+// ── MidHasher (streaming push API) ──────────────────────────
+
+#[test]
+fn mid_hasher_matches_mid_full_for_equivalent_value() {
+    // {"action": "deploy", "count": 3, "tags": ["a", "b"]}, driven through
+    // MidHasher's push events in the same canonical key order, must agree
+    // with mid_full over the equivalent MapValue tree.
+    let mut hasher = MidHasher::new();
+    hasher.begin_map(3).unwrap();
+    hasher.key("action").unwrap();
+    hasher.string("deploy").unwrap();
+    hasher.key("count").unwrap();
+    hasher.integer(3).unwrap();
+    hasher.key("tags").unwrap();
+    hasher.begin_list(2).unwrap();
+    hasher.string("a").unwrap();
+    hasher.string("b").unwrap();
+    hasher.end().unwrap();
+    hasher.end().unwrap();
+    let mid = hasher.finish().unwrap();
+
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("count".into(), MapValue::Integer(3)),
+        (
+            "tags".into(),
+            MapValue::List(vec![MapValue::String("a".into()), MapValue::String("b".into())]),
+        ),
+    ]);
+    assert_eq!(mid, mid_full(&val).unwrap());
+}
+
+#[test]
+fn mid_hasher_big_int_matches_mid_full() {
+    let mut hasher = MidHasher::new();
+    hasher.begin_map(1).unwrap();
+    hasher.key("n").unwrap();
+    hasher.big_int(false, &[0xab; 40]).unwrap();
+    hasher.end().unwrap();
+    let mid = hasher.finish().unwrap();
+
+    let val = MapValue::Map(vec![("n".into(), MapValue::BigInt(false, vec![0xab; 40]))]);
+    assert_eq!(mid, mid_full(&val).unwrap());
+}
+
+#[test]
+fn mid_hasher_big_int_rejects_non_minimal_magnitude() {
+    let mut hasher = MidHasher::new();
+    hasher.begin_map(1).unwrap();
+    hasher.key("n").unwrap();
+    let err = hasher.big_int(false, &[0x00, 0x01]).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_MCF);
+}
+
+#[test]
+fn mid_hasher_rejects_out_of_order_key() {
+    let mut hasher = MidHasher::new();
+    hasher.begin_map(2).unwrap();
+    hasher.key("b").unwrap();
+    hasher.string("1").unwrap();
+    let err = hasher.key("a").unwrap_err();
+    assert_eq!(err.code, ERR_KEY_ORDER);
+}
+
+#[test]
+fn mid_hasher_rejects_end_before_declared_count_reached() {
+    let mut hasher = MidHasher::new();
+    hasher.begin_map(2).unwrap();
+    hasher.key("a").unwrap();
+    hasher.string("1").unwrap();
+    let err = hasher.end().unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA);
+}
+
+// ── MapSchema / mid_full_checked (v1.1 extension) ───────────
+
+#[test]
+fn schema_passes_matching_descriptor() {
+    let schema = MapSchema::new()
+        .require("/action", SchemaType::String)
+        .unwrap()
+        .optional("/replicas", SchemaType::Integer)
+        .unwrap();
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("replicas".into(), MapValue::Integer(3)),
+    ]);
+    let mid = mid_full_checked(&val, &schema).unwrap();
+    assert_eq!(mid, mid_full(&val).unwrap());
+}
+
+#[test]
+fn schema_rejects_type_mismatch() {
+    let schema = MapSchema::new().require("/ok", SchemaType::Boolean).unwrap();
+    let val = MapValue::Map(vec![("ok".into(), MapValue::String("true".into()))]);
+    let err = mid_full_checked(&val, &schema).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA_MISMATCH);
+}
+
+#[test]
+fn schema_required_field_missing_is_mismatch() {
+    let schema = MapSchema::new().require("/action", SchemaType::String).unwrap();
+    let val = MapValue::Map(vec![("other".into(), MapValue::String("x".into()))]);
+    let err = mid_full_checked(&val, &schema).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA_MISMATCH);
+}
+
+#[test]
+fn schema_optional_field_absent_passes() {
+    let schema = MapSchema::new().optional("/replicas", SchemaType::Integer).unwrap();
+    let val = MapValue::Map(vec![("action".into(), MapValue::String("deploy".into()))]);
+    assert!(mid_full_checked(&val, &schema).is_ok());
+}
+
+#[test]
+fn schema_wildcard_matches_every_list_element() {
+    let schema = MapSchema::new().require("/flags/*", SchemaType::Boolean).unwrap();
+    let ok = MapValue::Map(vec![(
+        "flags".into(),
+        MapValue::List(vec![MapValue::Boolean(true), MapValue::Boolean(false)]),
+    )]);
+    assert!(mid_full_checked(&ok, &schema).is_ok());
+
+    let bad = MapValue::Map(vec![(
+        "flags".into(),
+        MapValue::List(vec![MapValue::Boolean(true), MapValue::String("no".into())]),
+    )]);
+    let err = mid_full_checked(&bad, &schema).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA_MISMATCH);
+}
+
+#[test]
+fn schema_forbid_additional_keys_rejects_undeclared_key() {
+    let schema = MapSchema::new()
+        .require("/action", SchemaType::String)
+        .unwrap()
+        .forbid_additional_keys();
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("extra".into(), MapValue::Boolean(true)),
+    ]);
+    let err = mid_full_checked(&val, &schema).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA_MISMATCH);
+}
+
+#[test]
+fn schema_non_map_root_rejected() {
+    let schema = MapSchema::new();
+    let val = MapValue::List(vec![]);
+    let err = mid_full_checked(&val, &schema).unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA);
+}
+
+// ── RawCanon (v1.1 extension) ───────────────────────────────
+
+#[test]
+fn raw_canon_get_empty_pointer_is_whole_document_mid() {
+    let val = MapValue::Map(vec![("a".into(), MapValue::String("1".into()))]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    assert_eq!(raw.get("").unwrap(), mid_full(&val).unwrap());
+}
+
+#[test]
+fn raw_canon_get_single_key_is_mid_of_its_value() {
+    let val = MapValue::Map(vec![
+        ("a".into(), MapValue::String("1".into())),
+        ("b".into(), MapValue::Integer(2)),
+    ]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    assert_eq!(raw.get("/a").unwrap(), mid_full(&MapValue::String("1".into())).unwrap());
+}
+
+#[test]
+fn raw_canon_get_resolves_nested_map_path() {
+    let val = MapValue::Map(vec![(
+        "a".into(),
+        MapValue::Map(vec![("b".into(), MapValue::String("x".into()))]),
+    )]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    let expected = mid_full(&MapValue::String("x".into())).unwrap();
+    assert_eq!(raw.get("/a/b").unwrap(), expected);
+}
+
+#[test]
+fn raw_canon_get_through_list_is_err_schema() {
+    let val = MapValue::Map(vec![("a".into(), MapValue::List(vec![MapValue::Boolean(true)]))]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    let err = raw.get("/a/0").unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA);
+}
+
+#[test]
+fn raw_canon_get_missing_key_is_err_schema() {
+    let val = MapValue::Map(vec![("a".into(), MapValue::String("1".into()))]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    let err = raw.get("/nonexistent").unwrap_err();
+    assert_eq!(err.code, ERR_SCHEMA);
+}
+
+#[test]
+fn raw_canon_keys_lists_root_map_keys_in_canonical_order() {
+    let val = MapValue::Map(vec![
+        ("a".into(), MapValue::String("1".into())),
+        ("b".into(), MapValue::String("2".into())),
+    ]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    assert_eq!(raw.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+}
+
+#[test]
+fn raw_canon_keys_empty_for_non_map_root() {
+    let val = MapValue::List(vec![MapValue::Boolean(true)]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    let raw = RawCanon::open(&canon).unwrap();
+    assert_eq!(raw.keys().count(), 0);
+}
+
+// ── JSON normalization / DupPolicy (v1.1 extension) ─────────
+
+#[test]
+fn normalized_json_sorts_unsorted_keys() {
+    let json = br#"{"b":"2","a":"1"}"#;
+    let mid = mid_full_json_normalized(json, DupPolicy::Reject).unwrap();
+    let expected = MapValue::Map(vec![
+        ("a".into(), MapValue::String("1".into())),
+        ("b".into(), MapValue::String("2".into())),
+    ]);
+    assert_eq!(mid, mid_full(&expected).unwrap());
+}
+
+#[test]
+fn normalized_json_reject_matches_strict_behavior() {
+    let json = br#"{"a":"1","a":"2"}"#;
+    let err = mid_full_json_normalized(json, DupPolicy::Reject).unwrap_err();
+    assert_eq!(err.code, ERR_DUP_KEY);
+}
+
+#[test]
+fn normalized_json_last_wins_keeps_later_duplicate() {
+    let json = br#"{"a":"1","a":"2"}"#;
+    let mid = mid_full_json_normalized(json, DupPolicy::LastWins).unwrap();
+    let expected = MapValue::Map(vec![("a".into(), MapValue::String("2".into()))]);
+    assert_eq!(mid, mid_full(&expected).unwrap());
+}
+
+#[test]
+fn normalized_json_first_wins_keeps_earlier_duplicate() {
+    let json = br#"{"a":"1","a":"2"}"#;
+    let mid = mid_full_json_normalized(json, DupPolicy::FirstWins).unwrap();
+    let expected = MapValue::Map(vec![("a".into(), MapValue::String("1".into()))]);
+    assert_eq!(mid, mid_full(&expected).unwrap());
+}
+
+#[test]
+fn normalized_json_matches_mid_full_for_already_canonical_input() {
+    // Normalization must be purely an ingestion convenience: already
+    // sorted, duplicate-free input produces exactly the MID mid_full would.
+    let val = MapValue::Map(vec![
+        ("a".into(), MapValue::String("1".into())),
+        ("b".into(), MapValue::String("2".into())),
+    ]);
+    let json = canon_value_to_json(&val);
+    let mid = mid_full_json_normalized(json.as_bytes(), DupPolicy::Reject).unwrap();
+    assert_eq!(mid, mid_full(&val).unwrap());
+}
+
+#[test]
+fn canonicalize_map_last_wins_keeps_later_duplicate() {
+    let entries = vec![
+        ("x".to_string(), MapValue::Integer(1)),
+        ("x".to_string(), MapValue::Integer(2)),
+    ];
+    let val = canonicalize_map(entries, DupPolicy::LastWins).unwrap();
+    assert_eq!(val, MapValue::Map(vec![("x".into(), MapValue::Integer(2))]));
+}
+
+// ── Streaming JSON-STRICT reader (v1.1 extension) ───────────
+
+#[test]
+fn json_strict_reader_matches_slice_path() {
+    let json = br#"{"action":"deploy","count":3}"#;
+    let mid_from_reader = mid_full_json_strict_reader(&json[..]).unwrap();
+    let mid_from_slice = mid_full_json(json).unwrap();
+    assert_eq!(mid_from_reader, mid_from_slice);
+}
+
+#[test]
+fn json_strict_reader_tolerates_unsorted_keys() {
+    // Unlike mid_full_json_reader (which requires already-sorted source
+    // keys), this path goes through the full MapValue tree, so unsorted
+    // input is accepted the same way mid_full_json accepts it.
+    let json = br#"{"b":"2","a":"1"}"#;
+    let mid = mid_full_json_strict_reader(&json[..]).unwrap();
+    let expected = MapValue::Map(vec![
+        ("a".into(), MapValue::String("1".into())),
+        ("b".into(), MapValue::String("2".into())),
+    ]);
+    assert_eq!(mid, mid_full(&expected).unwrap());
+}
+
+#[test]
+fn json_strict_reader_rejects_duplicate_key() {
+    let json = br#"{"a":"1","a":"2"}"#;
+    let err = mid_full_json_strict_reader(&json[..]).unwrap_err();
+    assert_eq!(err.code, ERR_DUP_KEY);
+}
+
+#[test]
+fn json_strict_reader_enforces_size_limit_incrementally() {
+    // LimitedReader's whole point is failing the instant bytes read cross
+    // MAX_CANON_BYTES, while still streaming in rather than only checking
+    // after the whole input is buffered. A reader that yields an
+    // oversized document only hits that check because LimitedReader
+    // wraps every read() call, not because the slice path pre-measured
+    // the input's length up front.
+    struct InfiniteSpaces;
+    impl std::io::Read for InfiniteSpaces {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            // An endless stream of JSON whitespace before a value that
+            // never starts: this can only be caught by a reader that
+            // enforces the limit as bytes arrive, not one that requires
+            // the whole input up front to measure it.
+            buf.fill(b' ');
+            Ok(buf.len())
+        }
+    }
+    let err = mid_full_json_strict_reader(InfiniteSpaces).unwrap_err();
+    assert_eq!(err.code, ERR_LIMIT_SIZE);
+}
+
+// ── NDJSON / mid_stream (v1.1 extension) ────────────────────
+
+#[test]
+fn mid_stream_yields_one_mid_per_line() {
+    let ndjson = b"{\"a\":1}\n{\"a\":2}\n";
+    let mids: Vec<String> = mid_stream(&ndjson[..]).map(|r| r.unwrap()).collect();
+    assert_eq!(mids.len(), 2);
+    assert_eq!(mids[0], mid_full_json(br#"{"a":1}"#).unwrap());
+    assert_eq!(mids[1], mid_full_json(br#"{"a":2}"#).unwrap());
+    assert_ne!(mids[0], mids[1]);
+}
+
+#[test]
+fn mid_stream_one_bad_record_does_not_abort_the_rest() {
+    let ndjson = b"{\"a\":1}\nnot json\n{\"a\":2}\n";
+    let results: Vec<Result<String, MapError>> = mid_stream(&ndjson[..]).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &mid_full_json(br#"{"a":2}"#).unwrap());
+}
+
+#[test]
+fn mid_stream_duplicate_key_is_err_dup_key() {
+    let ndjson = b"{\"a\":1,\"a\":2}\n";
+    let err = mid_stream(&ndjson[..]).next().unwrap().unwrap_err();
+    assert_eq!(err.code, ERR_DUP_KEY);
+}
+
+// ── StringProfile (v1.1 extension) ──────────────────────────
+
+#[test]
+fn string_profile_default_matches_surrogates_only() {
+    // surrogates_only() is meant to be indistinguishable from the
+    // default used when no profile is supplied at all.
+    let profile = StringProfile::default();
+    assert!(profile.validate("hello").is_ok());
+    for s in ["hello", "a\u{0001}b", "\u{E000}", "\u{FDD0}"] {
+        assert_eq!(
+            profile.validate(s).is_ok(),
+            StringProfile::surrogates_only().validate(s).is_ok()
+        );
+    }
+}
+
+#[test]
+fn string_profile_strict_rejects_c0_control() {
+    let profile = StringProfile::strict();
+    let err = profile.validate("a\u{0001}b").unwrap_err();
+    assert_eq!(err.code, ERR_UTF8);
+}
+
+#[test]
+fn string_profile_surrogates_only_accepts_private_use_and_noncharacter() {
+    // The default profile must remain surrogate-only: code points that
+    // strict() forbids are still fine here.
+    let profile = StringProfile::surrogates_only();
+    assert!(profile.validate("\u{E000}").is_ok()); // private-use area
+    assert!(profile.validate("\u{FDD0}").is_ok()); // noncharacter
+}
+
+#[test]
+fn string_profile_strict_rejects_private_use_area() {
+    let profile = StringProfile::strict();
+    let err = profile.validate("\u{E000}").unwrap_err();
+    assert_eq!(err.code, ERR_UTF8);
+}
+
+#[test]
+fn string_profile_strict_rejects_noncharacter() {
+    let profile = StringProfile::strict();
+    let err = profile.validate("\u{FDD0}").unwrap_err();
+    assert_eq!(err.code, ERR_UTF8);
+
+    let err = profile.validate("\u{1FFFE}").unwrap_err();
+    assert_eq!(err.code, ERR_UTF8);
+}
+
+#[test]
+fn mcf_encode_value_with_profile_rejects_disallowed_value() {
+    let val = MapValue::Map(vec![("a".into(), MapValue::String("\u{E000}".into()))]);
+    let err = mcf_encode_value_with_profile(&val, 0, &StringProfile::strict()).unwrap_err();
+    assert_eq!(err.code, ERR_UTF8);
+
+    // The default profile still lets the same value through.
+    assert!(mcf_encode_value_with_profile(&val, 0, &StringProfile::surrogates_only()).is_ok());
+}
+
+// ── CanonMmap (v1.1 extension) ──────────────────────────────
+
+/// A unique path under the OS temp dir, scoped to this test's name and
+/// process id so concurrent test runs don't collide.
+fn temp_canon_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("map1_test_{}_{}.canon", std::process::id(), name))
+}
+
+#[test]
+fn canon_mmap_open_and_hash_round_trips_mid_from_canon_bytes() {
+    let path = temp_canon_path("open_and_hash");
+    let val = MapValue::Map(vec![("action".into(), MapValue::String("deploy".into()))]);
+    let canon = canonical_bytes_full(&val).unwrap();
+    std::fs::write(&path, &canon).unwrap();
+
+    let mapped = CanonMmap::open(&path).unwrap();
+    assert_eq!(mapped.as_bytes(), canon.as_slice());
+    let mid = mid_from_canon_mmap(&mapped).unwrap();
+    assert_eq!(mid, mid_full(&val).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn canon_mmap_open_missing_file_is_err_io() {
+    let path = temp_canon_path("missing_file_does_not_exist");
+    let _ = std::fs::remove_file(&path); // make sure it really is absent
+    let err = CanonMmap::open(&path).unwrap_err();
+    assert_eq!(err.code, ERR_IO);
+}
+
+#[test]
+fn canon_mmap_rejects_bad_canon_hdr() {
+    let path = temp_canon_path("bad_hdr");
+    std::fs::write(&path, b"NOPE\x0012345").unwrap();
+
+    let mapped = CanonMmap::open(&path).unwrap();
+    let err = mid_from_canon_mmap(&mapped).unwrap_err();
+    assert_eq!(err.code, ERR_CANON_HDR);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+// ── serde_bridge: from_map_value (Deserialize direction) ────
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+struct Deployment {
+    action: String,
+    count: i64,
+    ok: bool,
+    tags: Vec<String>,
+}
+
+#[test]
+fn from_map_value_rebuilds_typed_struct() {
+    let val = MapValue::Map(vec![
+        ("action".into(), MapValue::String("deploy".into())),
+        ("count".into(), MapValue::Integer(3)),
+        ("ok".into(), MapValue::Boolean(true)),
+        (
+            "tags".into(),
+            MapValue::List(vec![MapValue::String("a".into()), MapValue::String("b".into())]),
+        ),
+    ]);
+    let deployment: Deployment = from_map_value(&val).unwrap();
+    assert_eq!(
+        deployment,
+        Deployment {
+            action: "deploy".into(),
+            count: 3,
+            ok: true,
+            tags: vec!["a".into(), "b".into()],
+        }
+    );
+}
+
+#[test]
+fn from_map_value_round_trips_through_to_map_value() {
+    let deployment = Deployment {
+        action: "deploy".into(),
+        count: 3,
+        ok: true,
+        tags: vec!["a".into(), "b".into()],
+    };
+    // Only to_map_value/mid_of were covered before; this exercises the
+    // other direction on the exact same struct.
+    let val = to_map_value(&deployment).unwrap();
+    let round_tripped: Deployment = from_map_value(&val).unwrap();
+    assert_eq!(round_tripped, deployment);
+}
+
+#[test]
+fn from_map_value_bigint_fitting_i128_visits_i128() {
+    let val = MapValue::big_int_from_decimal("9223372036854775808").unwrap(); // 2^63
+    let n: i128 = from_map_value(&val).unwrap();
+    assert_eq!(n, 9223372036854775808i128);
+}
+
+#[test]
+fn from_map_value_bigint_too_large_for_i128_falls_back_to_string() {
+    let val = MapValue::big_int_from_decimal("999999999999999999999999999999999999999").unwrap();
+    let s: String = from_map_value(&val).unwrap();
+    assert_eq!(s, "999999999999999999999999999999999999999");
+}
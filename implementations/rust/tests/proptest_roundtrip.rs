@@ -0,0 +1,130 @@
+//! Property-based round-trip and differential fuzz harness for the
+//! model/MCF/MID pipeline (v1.1 extension).
+//!
+//! `tests/conformance.rs` exercises 95 hand-picked vectors; this file
+//! generates random canonical `MapValue` trees instead, bounded to
+//! `MAX_TEST_DEPTH` (matching `MAX_DEPTH`), with MAP keys always emitted
+//! pre-sorted in canonical byte order (required by `MapValue::Map`'s own
+//! invariant) and integers drawn across the full `i64` range plus
+//! boundary values, weighted so the BigInt branch includes `2^63`, `2^64`,
+//! and large negatives — the same boundaries `tests/api.rs`'s BigInt
+//! tests cover by hand. `proptest` shrinks any failure to the smallest
+//! offending tree automatically; there's no separate corpus file to seed
+//! from since `conformance/conformance_vectors_v11.json` isn't present in
+//! this tree (`tests/conformance.rs` loads it from there), so known edge cases
+//! are folded into the generator itself instead.
+//!
+//! Invariants checked, one per `proptest!` block:
+//!   1. `mid_full` (model path) and `mid_from_canon_bytes` (fast path)
+//!      agree on the same value.
+//!   2. `value_from_canon_bytes` (the inverse decoder) recovers a value
+//!      equal to the one that was encoded.
+//!   3. A single-byte mutation of valid CANON_BYTES either fails
+//!      `mid_from_canon_bytes` outright, or — if it still happens to
+//!      validate — produces a different MID than the original. No
+//!      silent collisions under local corruption.
+
+use proptest::prelude::*;
+
+use map1::{canonical_bytes_full, mid_from_canon_bytes, mid_full, value_from_canon_bytes, MapValue};
+
+/// Matches `constants::MAX_DEPTH`; kept as a local literal so this file
+/// doesn't need `pub` access to the constant for a test-only bound.
+const MAX_TEST_DEPTH: u32 = 32;
+
+fn arb_map_key() -> impl Strategy<Value = String> {
+    "[a-z]{1,8}"
+}
+
+fn arb_bigint() -> impl Strategy<Value = MapValue> {
+    prop_oneof![
+        Just(MapValue::big_int_from_decimal("9223372036854775808").unwrap()), // 2^63
+        Just(MapValue::big_int_from_decimal("18446744073709551616").unwrap()), // 2^64
+        Just(MapValue::big_int_from_decimal("-99999999999999999999999999999999999999").unwrap()),
+        proptest::collection::vec(any::<u8>(), 1..8usize).prop_map(|mut bytes| {
+            while bytes.first() == Some(&0) && bytes.len() > 1 {
+                bytes.remove(0);
+            }
+            // An all-zero draw survives the loop above as `[0]` (it never
+            // strips the last byte), which the encoder rejects as a
+            // non-minimal magnitude. Collapse to the canonical non-negative
+            // zero, matching `big_int_from_decimal`'s own normalization.
+            if bytes == vec![0] {
+                bytes.clear();
+            }
+            MapValue::BigInt(false, bytes)
+        }),
+    ]
+}
+
+fn arb_scalar() -> impl Strategy<Value = MapValue> {
+    prop_oneof![
+        any::<bool>().prop_map(MapValue::Boolean),
+        prop_oneof![Just(0i64), Just(i64::MIN), Just(i64::MAX), any::<i64>()]
+            .prop_map(MapValue::Integer),
+        "[a-zA-Z0-9 ]{0,16}".prop_map(MapValue::String),
+        proptest::collection::vec(any::<u8>(), 0..16usize).prop_map(MapValue::Bytes),
+        arb_bigint(),
+    ]
+}
+
+/// A bounded-depth canonical `MapValue` tree. LIST entries are plain
+/// recursive values; MAP entries are generated as (key, value) pairs and
+/// then sorted and deduped by raw key bytes so every generated `Map` is
+/// already in the pre-sorted form the encoder requires — an unsorted or
+/// duplicate-keyed tree would just be rejected with `ERR_KEY_ORDER` /
+/// `ERR_DUP_KEY` rather than exercising the round-trip invariants below.
+fn arb_map_value() -> impl Strategy<Value = MapValue> {
+    arb_scalar().prop_recursive(MAX_TEST_DEPTH, 64, 8, |inner| {
+        prop_oneof![
+            proptest::collection::vec(inner.clone(), 0..8usize).prop_map(MapValue::List),
+            proptest::collection::vec((arb_map_key(), inner), 0..8usize).prop_map(|mut entries| {
+                entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+                entries.dedup_by(|(a, _), (b, _)| a == b);
+                MapValue::Map(entries)
+            }),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn model_and_fast_path_agree(val in arb_map_value()) {
+        let canon = canonical_bytes_full(&val).unwrap();
+        let from_model = mid_full(&val).unwrap();
+        let from_bytes = mid_from_canon_bytes(&canon).unwrap();
+        prop_assert_eq!(from_model, from_bytes);
+    }
+
+    #[test]
+    fn inverse_decode_round_trips(val in arb_map_value()) {
+        let canon = canonical_bytes_full(&val).unwrap();
+        let decoded = value_from_canon_bytes(&canon).unwrap();
+        prop_assert_eq!(decoded, val);
+    }
+
+    #[test]
+    fn single_byte_mutation_never_silently_collides(
+        val in arb_map_value(),
+        byte_idx in any::<usize>(),
+        replacement in any::<u8>(),
+    ) {
+        let canon = canonical_bytes_full(&val).unwrap();
+        if canon.is_empty() {
+            return Ok(());
+        }
+        let idx = byte_idx % canon.len();
+        if canon[idx] == replacement {
+            return Ok(());
+        }
+
+        let original_mid = mid_from_canon_bytes(&canon).unwrap();
+        let mut mutated = canon.clone();
+        mutated[idx] = replacement;
+
+        match mid_from_canon_bytes(&mutated) {
+            Err(_) => {} // rejected outright — satisfies the invariant
+            Ok(mutated_mid) => prop_assert_ne!(mutated_mid, original_mid),
+        }
+    }
+}